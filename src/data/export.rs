@@ -0,0 +1,340 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::io::{self, Write};
+
+use super::structs::{ColumnDescription, ResultSet, ValueType};
+use crate::database::oid;
+
+/// Format the rows of a result are exported in.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ResultFormat {
+    #[default]
+    Csv,
+    /// Array of objects keyed by column name.
+    Json,
+    /// Object per line.
+    NdJson,
+    /// `INSERT` statements adding the rows to a table.
+    Insert,
+    Markdown,
+    Html,
+}
+
+impl ResultFormat {
+    pub const ALL: [ResultFormat; 6] = [
+        ResultFormat::Csv, ResultFormat::Json, ResultFormat::NdJson,
+        ResultFormat::Insert, ResultFormat::Markdown, ResultFormat::Html,
+    ];
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ResultFormat::Csv => "csv",
+            ResultFormat::Json => "json",
+            ResultFormat::NdJson => "ndjson",
+            ResultFormat::Insert => "sql",
+            ResultFormat::Markdown => "md",
+            ResultFormat::Html => "html",
+        }
+    }
+}
+
+impl fmt::Display for ResultFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ResultFormat::Csv => write!(f, "CSV"),
+            ResultFormat::Json => write!(f, "JSON"),
+            ResultFormat::NdJson => write!(f, "NDJSON"),
+            ResultFormat::Insert => write!(f, "SQL INSERT"),
+            ResultFormat::Markdown => write!(f, "Markdown"),
+            ResultFormat::Html => write!(f, "HTML"),
+        }
+    }
+}
+
+/// Format selected result cells are copied to the clipboard in.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ClipboardFormat {
+    /// Tab separated values, which paste into spreadsheets.
+    Tsv,
+    Csv,
+    Json,
+    Insert,
+    Markdown,
+    /// `WHERE` condition matching the selected values.
+    WhereIn,
+}
+
+/// How the rows of a result are exported.
+#[derive(Clone, Debug)]
+pub struct ExportOptions {
+    pub format: ResultFormat,
+    /// Table the `INSERT` statements add rows to, written as is.
+    pub table_name: String,
+    /// Rows per `INSERT` statement.
+    pub batch_size: u32,
+    /// Text written for NULL in CSV files.
+    pub csv_null: String,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self { format: ResultFormat::Csv, table_name: String::from("table_name"), batch_size: 100, csv_null: String::new() }
+    }
+}
+
+/// Writes the columns and rows of the result in the format of the options.
+pub fn write_result(result: &ResultSet, options: &ExportOptions, writer: impl Write) -> Result<(), String> {
+    match options.format {
+        ResultFormat::Csv => write_separated(result, b',', &options.csv_null, true, writer),
+        ResultFormat::Json => write_json(result, writer),
+        ResultFormat::NdJson => write_ndjson(result, writer),
+        ResultFormat::Insert => write_insert(result, &options.table_name, options.batch_size.max(1) as usize, writer),
+        ResultFormat::Markdown => write_markdown(result, writer),
+        ResultFormat::Html => write_html(result, writer),
+    }
+    .map_err(|e| e.to_string())
+}
+
+/// Text of the cells in the format, `headers` adds the column names to TSV and CSV.
+/// Other formats name the columns anyway.
+pub fn clipboard_text(cells: &ResultSet, format: ClipboardFormat, options: &ExportOptions, headers: bool) -> String {
+    let mut text = Vec::new();
+
+    let result = match format {
+        ClipboardFormat::Tsv => write_separated(cells, b'\t', "", headers, &mut text),
+        ClipboardFormat::Csv => write_separated(cells, b',', &options.csv_null, headers, &mut text),
+        ClipboardFormat::Json => write_json(cells, &mut text),
+        ClipboardFormat::Insert => write_insert(cells, &options.table_name, options.batch_size.max(1) as usize, &mut text),
+        ClipboardFormat::Markdown => write_markdown(cells, &mut text),
+        ClipboardFormat::WhereIn => write_where_in(cells, &mut text),
+    };
+
+    match result {
+        Ok(()) => {
+            let text = String::from_utf8_lossy(&text);
+            text.strip_suffix('\n').unwrap_or(&text).to_string()
+        },
+        Err(e) => e.to_string(),
+    }
+}
+
+fn write_separated(result: &ResultSet, delimiter: u8, null: &str, headers: bool, writer: impl Write) -> io::Result<()> {
+    let mut writer = csv::WriterBuilder::new().delimiter(delimiter).from_writer(writer);
+
+    if headers {
+        writer.write_record(result.columns.iter().map(|column| column.name.as_str()))?;
+    }
+
+    for row in &result.rows {
+        let record = row
+            .iter()
+            .map(|value| match value {
+                ValueType::Null => Ok(null.to_string()),
+                value => text_value(value),
+            })
+            .collect::<io::Result<Vec<String>>>()?;
+
+        writer.write_record(record)?;
+    }
+
+    writer.flush()
+}
+
+fn write_json(result: &ResultSet, mut writer: impl Write) -> io::Result<()> {
+    writeln!(writer, "[")?;
+
+    for (index, row) in result.rows.iter().enumerate() {
+        let separator = if index + 1 < result.rows.len() { "," } else { "" };
+        writeln!(writer, "  {}{}", json_object(&result.columns, row)?, separator)?;
+    }
+
+    writeln!(writer, "]")
+}
+
+fn write_ndjson(result: &ResultSet, mut writer: impl Write) -> io::Result<()> {
+    for row in &result.rows {
+        writeln!(writer, "{}", json_object(&result.columns, row)?)?;
+    }
+
+    Ok(())
+}
+
+fn write_insert(result: &ResultSet, table_name: &str, batch_size: usize, mut writer: impl Write) -> io::Result<()> {
+    let columns = result.columns
+        .iter()
+        .map(|column| format!("\"{}\"", column.name.replace('"', "\"\"")))
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    for batch in result.rows.chunks(batch_size) {
+        writeln!(writer, "INSERT INTO {} ({}) VALUES", table_name, columns)?;
+
+        for (index, row) in batch.iter().enumerate() {
+            let values = row.iter().map(sql_literal).collect::<io::Result<Vec<String>>>()?.join(", ");
+            let separator = if index + 1 < batch.len() { "," } else { ";" };
+
+            writeln!(writer, "    ({}){}", values, separator)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn write_where_in(result: &ResultSet, mut writer: impl Write) -> io::Result<()> {
+    let columns = result.columns
+        .iter()
+        .map(|column| format!("\"{}\"", column.name.replace('"', "\"\"")))
+        .collect::<Vec<String>>();
+
+    // Repeated values are listed once, in the order they come
+    let mut seen = HashSet::new();
+    let values = result.rows
+        .iter()
+        .map(|row| match row.as_slice() {
+            [value] => sql_literal(value),
+            row => Ok(format!("({})", row.iter().map(sql_literal).collect::<io::Result<Vec<String>>>()?.join(", "))),
+        })
+        .filter(|value| value.as_ref().map_or(true, |value| seen.insert(value.clone())))
+        .collect::<io::Result<Vec<String>>>()?;
+
+    match columns.as_slice() {
+        [column] => writeln!(writer, "WHERE {} IN ({})", column, values.join(", ")),
+        columns => writeln!(writer, "WHERE ({}) IN ({})", columns.join(", "), values.join(", ")),
+    }
+}
+
+fn write_markdown(result: &ResultSet, mut writer: impl Write) -> io::Result<()> {
+    let cell = |text: &str| text.replace('\\', "\\\\").replace('|', "\\|").replace("\r\n", "<br>").replace('\n', "<br>");
+
+    writeln!(writer, "| {} |", result.columns.iter().map(|column| cell(&column.name)).collect::<Vec<String>>().join(" | "))?;
+
+    // Numbers are aligned to the right
+    let alignments = result.columns
+        .iter()
+        .enumerate()
+        .map(|(index, _)| {
+            let numeric = result.rows.iter().any(|row| is_number(&row[index]))
+                && result.rows.iter().all(|row| is_number(&row[index]) || matches!(row[index], ValueType::Null));
+
+            if numeric { "---:" } else { "---" }
+        })
+        .collect::<Vec<&str>>();
+    writeln!(writer, "| {} |", alignments.join(" | "))?;
+
+    for row in &result.rows {
+        let cells = row.iter().map(|value| Ok(cell(&text_value(value)?))).collect::<io::Result<Vec<String>>>()?;
+        writeln!(writer, "| {} |", cells.join(" | "))?;
+    }
+
+    Ok(())
+}
+
+fn write_html(result: &ResultSet, mut writer: impl Write) -> io::Result<()> {
+    writeln!(writer, "<table>")?;
+    writeln!(writer, "  <thead>")?;
+    writeln!(writer, "    <tr>{}</tr>", result.columns.iter().map(|column| format!("<th>{}</th>", html_escape(&column.name))).collect::<String>())?;
+    writeln!(writer, "  </thead>")?;
+    writeln!(writer, "  <tbody>")?;
+
+    for row in &result.rows {
+        let cells = row
+            .iter()
+            .map(|value| match value {
+                ValueType::Null => Ok(String::from("<td><i>NULL</i></td>")),
+                value if is_number(value) => Ok(format!("<td align=\"right\">{}</td>", html_escape(&text_value(value)?))),
+                value => Ok(format!("<td>{}</td>", html_escape(&text_value(value)?))),
+            })
+            .collect::<io::Result<String>>()?;
+
+        writeln!(writer, "    <tr>{}</tr>", cells)?;
+    }
+
+    writeln!(writer, "  </tbody>")?;
+    writeln!(writer, "</table>")
+}
+
+fn is_number(value: &ValueType) -> bool {
+    matches!(value, ValueType::Int(_) | ValueType::BigInt(_) | ValueType::Float(_) | ValueType::Numeric(_))
+}
+
+/// Text of the value for text formats.
+fn text_value(value: &ValueType) -> io::Result<String> {
+    check_decoded(value)?;
+
+    Ok(value.to_string())
+}
+
+/// Fails on a value the app couldn't read, also nested in an array, record or range,
+/// so the export stops rather than writing the type name or the error as data.
+fn check_decoded(value: &ValueType) -> io::Result<()> {
+    match value {
+        ValueType::Unknown(type_name) => Err(io::Error::other(format!("Values of type {} can't be exported", type_name))),
+        ValueType::DecodeError(error) => Err(io::Error::other(format!("A value failed to decode: {}", error))),
+        ValueType::Array(values, _) => values.iter().try_for_each(check_decoded),
+        ValueType::Record(fields) => fields.iter().try_for_each(|(_, value)| check_decoded(value)),
+        ValueType::Range(Some(range)) => range.lower.iter().chain(&range.upper).try_for_each(check_decoded),
+        _ => Ok(()),
+    }
+}
+
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+fn json_string(text: &str) -> String {
+    serde_json::Value::String(text.to_string()).to_string()
+}
+
+fn json_object(columns: &[ColumnDescription], row: &[ValueType]) -> io::Result<String> {
+    let fields = columns
+        .iter()
+        .zip(row)
+        .map(|(column, value)| {
+            let value = match value {
+                // Documents are embedded rather than quoted
+                ValueType::Text(json) if column.type_oid == oid::JSON || column.type_oid == oid::JSONB => json.clone(),
+                value => json_value(value)?,
+            };
+
+            Ok(format!("{}: {}", json_string(&column.name), value))
+        })
+        .collect::<io::Result<Vec<String>>>()?;
+
+    Ok(format!("{{{}}}", fields.join(", ")))
+}
+
+fn json_value(value: &ValueType) -> io::Result<String> {
+    Ok(match value {
+        ValueType::Null => String::from("null"),
+        ValueType::Int(int) => int.to_string(),
+        ValueType::BigInt(big_int) => big_int.to_string(),
+        ValueType::Float(float) if float.is_finite() => float.to_string(),
+        // Written as is to keep the precision, NaN and infinities are not JSON numbers
+        ValueType::Numeric(numeric) if numeric.parse::<f64>().is_ok_and(f64::is_finite) => numeric.clone(),
+        ValueType::Bool(bool) => bool.to_string(),
+        ValueType::Array(values, _) => format!("[{}]", values.iter().map(json_value).collect::<io::Result<Vec<String>>>()?.join(", ")),
+        ValueType::Record(fields) => format!(
+            "{{{}}}",
+            fields
+                .iter()
+                .map(|(name, value)| Ok(format!("{}: {}", json_string(name), json_value(value)?)))
+                .collect::<io::Result<Vec<String>>>()?
+                .join(", ")
+        ),
+        value => json_string(&text_value(value)?),
+    })
+}
+
+fn sql_literal(value: &ValueType) -> io::Result<String> {
+    let quote = |text: &str| format!("'{}'", text.replace('\'', "''"));
+
+    Ok(match value {
+        ValueType::Null => String::from("NULL"),
+        ValueType::Int(int) => int.to_string(),
+        ValueType::BigInt(big_int) => big_int.to_string(),
+        ValueType::Float(float) if float.is_finite() => float.to_string(),
+        ValueType::Numeric(numeric) if numeric.parse::<f64>().is_ok_and(f64::is_finite) => numeric.clone(),
+        ValueType::Bool(bool) => bool.to_string().to_uppercase(),
+        value => quote(&text_value(value)?),
+    })
+}
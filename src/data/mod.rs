@@ -1,3 +1,5 @@
+pub mod connection_string;
+pub mod export;
 pub mod structs;
 pub mod icons;
 pub mod scripts;
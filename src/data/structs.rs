@@ -1,11 +1,12 @@
 use serde::{Deserialize, Serialize};
+use sqlx_postgres::{PgConnectOptions, PgSslMode};
 
-use indexmap::IndexMap;
-
+use std::sync::atomic::AtomicBool;
 use std::sync::{Arc, Mutex};
 
 use egui::{Color32, Theme as EguiTheme};
 
+use crate::data::connection_string::ConnectionString;
 use crate::data::translates::Language;
 
 #[derive(Serialize, Deserialize, Clone, Debug, Default)]
@@ -23,6 +24,131 @@ pub struct Server {
     pub user: String,
     pub password: String,
     pub service_database: String,
+    /// Seconds a query from the SQL page may run, 0 for no limit.
+    #[serde(default)]
+    pub statement_timeout: u32,
+    /// Seconds a query from the SQL page may wait for a lock, 0 for no limit.
+    #[serde(default)]
+    pub lock_timeout: u32,
+    #[serde(default)]
+    pub ssl_mode: SslMode,
+    /// Path to the CA certificates the server certificate is verified against,
+    /// the Mozilla root certificates built into the app if not set.
+    #[serde(default)]
+    pub ssl_root_cert: Option<String>,
+    /// Path to the certificate the client authenticates with.
+    #[serde(default)]
+    pub ssl_client_cert: Option<String>,
+    /// Path to the private key of the client certificate.
+    #[serde(default)]
+    pub ssl_client_key: Option<String>,
+    /// Name the connections report to the server, as in `pg_stat_activity`.
+    #[serde(default)]
+    pub application_name: Option<String>,
+    /// Settings sent when a connection starts, the `options` parameter of libpq.
+    #[serde(default)]
+    pub options: Option<String>,
+}
+
+impl Server {
+    pub fn query_timeouts(&self) -> crate::database::QueryTimeouts {
+        crate::database::QueryTimeouts {
+            statement_timeout: self.statement_timeout,
+            lock_timeout: self.lock_timeout,
+        }
+    }
+
+    /// Options of a connection to the database of the server.
+    /// An address starting with `/` is the directory of the Unix-domain socket.
+    pub fn connect_options(&self, database: &str) -> PgConnectOptions {
+        let mut options = PgConnectOptions::new_without_pgpass()
+            .port(self.port)
+            .username(&self.user)
+            .database(database);
+
+        options = if self.ip.starts_with('/') {
+            options.socket(&self.ip)
+        } else {
+            options.host(self.ip.trim_start_matches('[').trim_end_matches(']'))
+        };
+        if !self.password.is_empty() {
+            options = options.password(&self.password);
+        }
+        if let Some(application_name) = &self.application_name {
+            options = options.application_name(application_name);
+        }
+        if let Some(startup_options) = &self.options {
+            // sqlx writes the settings as they are, the server splits them at spaces
+            let escape = |text: String| text.replace('\\', "\\\\").replace(' ', "\\ ");
+            let settings = crate::data::connection_string::parse_options(startup_options).unwrap_or_default();
+            options = options.options(settings.into_iter().map(|(name, value)| (escape(name), escape(value))));
+        }
+
+        self.with_tls(options)
+    }
+
+    /// Applies the TLS settings of the server to the options of a connection.
+    pub fn with_tls(&self, options: PgConnectOptions) -> PgConnectOptions {
+        let mut options = options.ssl_mode(self.ssl_mode.to_sqlx());
+
+        if let Some(path) = &self.ssl_root_cert {
+            options = options.ssl_root_cert(path);
+        }
+        if let Some(path) = &self.ssl_client_cert {
+            options = options.ssl_client_cert(path);
+        }
+        if let Some(path) = &self.ssl_client_key {
+            options = options.ssl_client_key(path);
+        }
+
+        options
+    }
+}
+
+/// Whether and how a connection uses TLS, named like the `sslmode` of libpq.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum SslMode {
+    Disable,
+    Allow,
+    #[default]
+    Prefer,
+    Require,
+    /// Requires TLS and a server certificate signed by a trusted CA.
+    VerifyCa,
+    /// Like `VerifyCa`, and the certificate must also match the host name.
+    VerifyFull,
+}
+
+impl SslMode {
+    pub const ALL: [SslMode; 6] = [
+        SslMode::Disable, SslMode::Allow, SslMode::Prefer,
+        SslMode::Require, SslMode::VerifyCa, SslMode::VerifyFull,
+    ];
+
+    fn to_sqlx(self) -> PgSslMode {
+        match self {
+            SslMode::Disable => PgSslMode::Disable,
+            SslMode::Allow => PgSslMode::Allow,
+            SslMode::Prefer => PgSslMode::Prefer,
+            SslMode::Require => PgSslMode::Require,
+            SslMode::VerifyCa => PgSslMode::VerifyCa,
+            SslMode::VerifyFull => PgSslMode::VerifyFull,
+        }
+    }
+}
+
+impl std::fmt::Display for SslMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SslMode::Disable => write!(f, "disable"),
+            SslMode::Allow => write!(f, "allow"),
+            SslMode::Prefer => write!(f, "prefer"),
+            SslMode::Require => write!(f, "require"),
+            SslMode::VerifyCa => write!(f, "verify-ca"),
+            SslMode::VerifyFull => write!(f, "verify-full"),
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -30,6 +156,18 @@ pub struct Settings {
     pub scale_factor: f32,
     pub theme: Theme,
     pub language: Language,
+    pub display_time_zone: DisplayTimeZone,
+    pub csv_null: String,
+    pub max_rows: u32,
+}
+
+/// Time zone that `timestamp with time zone` values are shown in.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DisplayTimeZone {
+    Utc,
+    Local,
+    Session,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -67,7 +205,7 @@ impl Theme {
 
 impl Default for Settings {
     fn default() -> Self {
-        Self { scale_factor: 1.125, theme: Theme::NotInited, language: Language::English }
+        Self { scale_factor: 1.125, theme: Theme::NotInited, language: Language::English, display_time_zone: DisplayTimeZone::Utc, csv_null: String::new(), max_rows: 10000 }
     }
 }
 
@@ -79,6 +217,17 @@ pub struct AddServerWindow {
     pub user_field: String,
     pub password_field: String,
     pub service_database_field: String,
+    pub statement_timeout_field: String,
+    pub lock_timeout_field: String,
+    pub ssl_mode: SslMode,
+    pub ssl_root_cert_field: String,
+    pub ssl_client_cert_field: String,
+    pub ssl_client_key_field: String,
+    /// libpq URI or `key=value` string mirroring the connection fields.
+    pub connection_string_field: String,
+    /// Only set through the connection string.
+    pub application_name: Option<String>,
+    pub options: Option<String>,
 }
 
 impl Default for AddServerWindow {
@@ -91,8 +240,118 @@ impl Default for AddServerWindow {
             user_field: String::new(),
             password_field: String::new(),
             service_database_field: String::from("postgres"),
+            statement_timeout_field: String::from("0"),
+            lock_timeout_field: String::from("0"),
+            ssl_mode: SslMode::default(),
+            ssl_root_cert_field: String::new(),
+            ssl_client_cert_field: String::new(),
+            ssl_client_key_field: String::new(),
+            connection_string_field: String::new(),
+            application_name: None,
+            options: None,
+        }
+    }
+}
+
+impl AddServerWindow {
+    /// URI of the connection fields, empty until an address or a user is entered.
+    pub fn connection_uri(&self) -> String {
+        if self.ip_field.is_empty() && self.user_field.is_empty() {
+            return String::new();
+        }
+
+        let optional = |field: &str| Some(field.to_string()).filter(|field| !field.is_empty());
+
+        ConnectionString {
+            host: optional(&self.ip_field),
+            port: self.port_field.parse::<u16>().ok(),
+            user: optional(&self.user_field),
+            password: optional(&self.password_field),
+            dbname: optional(&self.service_database_field),
+            ssl_mode: Some(self.ssl_mode).filter(|ssl_mode| *ssl_mode != SslMode::default()),
+            ssl_root_cert: optional(&self.ssl_root_cert_field),
+            ssl_client_cert: optional(&self.ssl_client_cert_field),
+            ssl_client_key: optional(&self.ssl_client_key_field),
+            application_name: self.application_name.clone(),
+            options: self.options.clone(),
         }
+        .to_uri()
     }
+
+    /// Fills the connection fields from the connection string, the ones it doesn't set get their defaults.
+    pub fn apply_connection_string(&mut self, connection_string: ConnectionString) {
+        let default = Self::default();
+
+        self.ip_field = connection_string.host.unwrap_or(default.ip_field);
+        self.port_field = connection_string.port.map(|port| port.to_string()).unwrap_or(default.port_field);
+        self.user_field = connection_string.user.unwrap_or(default.user_field);
+        self.password_field = connection_string.password.unwrap_or(default.password_field);
+        self.service_database_field = connection_string.dbname.unwrap_or(default.service_database_field);
+        self.ssl_mode = connection_string.ssl_mode.unwrap_or(default.ssl_mode);
+        self.ssl_root_cert_field = connection_string.ssl_root_cert.unwrap_or(default.ssl_root_cert_field);
+        self.ssl_client_cert_field = connection_string.ssl_client_cert.unwrap_or(default.ssl_client_cert_field);
+        self.ssl_client_key_field = connection_string.ssl_client_key.unwrap_or(default.ssl_client_key_field);
+        self.application_name = connection_string.application_name;
+        self.options = connection_string.options;
+    }
+}
+
+/// Form for the placeholder values of statements waiting to run.
+#[derive(Default)]
+pub struct ParametersWindow {
+    pub show: bool,
+    pub page_index: Option<usize>,
+    /// Statements to run with their byte ranges in the page code.
+    pub statements: Vec<(String, Option<std::ops::Range<usize>>)>,
+    pub executed_range: Option<std::ops::Range<usize>>,
+    /// Placeholders with their text and whether NULL is bound instead.
+    pub values: Vec<(crate::database::script::Parameter, String, bool)>,
+    /// The statement is exported into a file rather than run.
+    pub export: bool,
+}
+
+/// Columns of a table loaded in the background, `None` until they arrive.
+pub type LoadingColumns = Arc<Mutex<Option<Result<Vec<String>, String>>>>;
+
+/// Import of a CSV file into a table with `COPY`.
+#[derive(Default)]
+pub struct ImportWindow {
+    pub show: bool,
+    pub database: Option<crate::database::Database>,
+    pub table: Table,
+    pub file_path: Option<std::path::PathBuf>,
+    /// Options the preview was read with.
+    pub options: crate::database::copy::CsvOptions,
+    pub delimiter_field: String,
+    pub quote_field: String,
+    pub preview: Option<Result<crate::database::copy::CsvPreview, String>>,
+    pub table_columns: LoadingColumns,
+    /// Table column each file column goes to, `None` skips the file column.
+    pub mapping: Vec<Option<String>>,
+    pub progress: Option<crate::database::copy::CopyProgress>,
+}
+
+/// Export of a table or a query into a file with `COPY`.
+#[derive(Default)]
+pub struct ExportWindow {
+    pub show: bool,
+    pub database: Option<crate::database::Database>,
+    pub source: Option<crate::database::copy::CopySource>,
+    pub format: crate::database::copy::CopyFormat,
+    pub progress: Option<crate::database::copy::CopyProgress>,
+}
+
+/// Format picker for exporting the result shown on a SQL page, keeping the last choice.
+#[derive(Default)]
+pub struct ResultExportWindow {
+    pub show: bool,
+    pub options: crate::data::export::ExportOptions,
+}
+
+#[derive(Default)]
+pub struct ClosePageWindow {
+    pub show: bool,
+    pub page_index: Option<usize>,
 }
 
 #[derive(Default)]
@@ -110,6 +369,12 @@ pub struct EditServerWindow {
     pub user_field: String,
     pub password_field: String,
     pub service_database_field: String,
+    pub statement_timeout_field: String,
+    pub lock_timeout_field: String,
+    pub ssl_mode: SslMode,
+    pub ssl_root_cert_field: String,
+    pub ssl_client_cert_field: String,
+    pub ssl_client_key_field: String,
     pub server: Option<Server>,
     pub original_server: Option<Server>,
 }
@@ -126,6 +391,9 @@ pub struct SettingsWindow {
     pub scale_factor: f32,
     pub theme: Theme,
     pub language: Option<Language>,
+    pub display_time_zone: Option<DisplayTimeZone>,
+    pub csv_null: Option<String>,
+    pub max_rows: Option<u32>,
 }
 
 impl Default for SettingsWindow {
@@ -135,6 +403,9 @@ impl Default for SettingsWindow {
             scale_factor: 0.0,
             theme: Theme::NotInited,
             language: None,
+            display_time_zone: None,
+            csv_null: None,
+            max_rows: None,
         }
     }
 }
@@ -183,11 +454,30 @@ pub struct Icons<'a> {
     pub rs_postgres: egui::Image<'a>,
 }
 
+/// Table of a database with the schema it belongs to.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Table {
+    pub schema: String,
+    pub name: String,
+}
+
+impl std::fmt::Display for Table {
+    /// Shows the schema unless it's `public`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.schema.as_str() {
+            "public" => write!(f, "{}", self.name),
+            schema => write!(f, "{}.{}", schema, self.name),
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct LoadedDatabase {
     pub name: String,
     pub database: crate::database::Database,
-    pub tables: Vec<String>,
+    pub tables: Vec<Table>,
+    /// TLS the connections negotiated, `None` if they are not encrypted.
+    pub tls: Option<crate::database::TlsState>,
 }
 
 #[derive(Clone)]
@@ -199,19 +489,110 @@ pub enum DbState {
 
 #[derive(Clone, Debug)]
 pub struct SQLQueryExecutionSuccess {
-    pub result: IndexMap<String, Vec<ValueType>>,
-    pub current_page: Option<IndexMap<String, Vec<ValueType>>>,
+    pub result: ResultSet,
     pub pages_count: u32,
     pub rows_count: u32,
+    pub rows_affected: u64,
     pub execution_time: u64,
     pub page_index: u32,
+    /// Rows are still being received.
+    pub fetching: bool,
+    /// The query has rows left in its cursor.
+    pub has_more: bool,
+    /// Plan found in the result of `EXPLAIN (FORMAT JSON)`.
+    pub plan: Option<Box<crate::database::explain::QueryPlan>>,
+    /// Command tag of the statement once all of its rows are read, such as `UPDATE 10`.
+    pub command_tag: Option<String>,
+}
+
+impl SQLQueryExecutionSuccess {
+    pub fn update_rows_count(&mut self) {
+        self.rows_count = self.result.rows.len() as u32;
+        self.pages_count = (self.rows_count as f32 / super::ROWS_PER_PAGE as f32).ceil() as u32;
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum FetchAction {
+    More,
+    All,
 }
 
 #[derive(Clone, Debug)]
 pub enum SQLQueryExecutionStatusType {
+    /// Waiting for the statements before it.
+    Pending,
     Running,
     Success(SQLQueryExecutionSuccess),
-    Error(String),
+    Error(crate::database::QueryError),
+    Cancelled,
+    /// Not run because a statement before it failed or was cancelled.
+    Skipped,
+}
+
+/// Description of a result column as sent by the server.
+#[derive(Clone, Debug)]
+pub struct ColumnDescription {
+    pub name: String,
+    pub type_oid: u32,
+    pub type_name: String,
+    /// Whether the source table column accepts NULL, `None` for computed columns.
+    pub nullable: Option<bool>,
+}
+
+/// Rows returned by a query, every row has a value for each of `columns` in the same order.
+#[derive(Clone, Debug, Default)]
+pub struct ResultSet {
+    pub columns: Vec<ColumnDescription>,
+    pub rows: Vec<Vec<ValueType>>,
+}
+
+impl ResultSet {
+    pub fn is_empty(&self) -> bool {
+        self.columns.is_empty()
+    }
+
+    pub fn page(&self, page_index: u32, rows_per_page: u32) -> &[Vec<ValueType>] {
+        let start_index = (page_index as usize * rows_per_page as usize).min(self.rows.len());
+        let end_index = (start_index + rows_per_page as usize).min(self.rows.len());
+
+        &self.rows[start_index..end_index]
+    }
+
+    /// Copy of the rows and columns in the ranges.
+    pub fn slice(&self, rows: std::ops::RangeInclusive<usize>, columns: std::ops::RangeInclusive<usize>) -> ResultSet {
+        ResultSet {
+            columns: self.columns[columns.clone()].to_vec(),
+            rows: self.rows[rows].iter().map(|row| row[columns.clone()].to_vec()).collect(),
+        }
+    }
+}
+
+/// Rectangle of result cells selected on a SQL page, by row and column of the whole result.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GridSelection {
+    /// Cell the selection started at.
+    pub anchor: (usize, usize),
+    /// Cell the selection was extended to by dragging or shift-clicking.
+    pub cursor: (usize, usize),
+}
+
+impl GridSelection {
+    pub fn cell(row: usize, column: usize) -> Self {
+        Self { anchor: (row, column), cursor: (row, column) }
+    }
+
+    pub fn rows(&self) -> std::ops::RangeInclusive<usize> {
+        self.anchor.0.min(self.cursor.0)..=self.anchor.0.max(self.cursor.0)
+    }
+
+    pub fn columns(&self) -> std::ops::RangeInclusive<usize> {
+        self.anchor.1.min(self.cursor.1)..=self.anchor.1.max(self.cursor.1)
+    }
+
+    pub fn contains(&self, row: usize, column: usize) -> bool {
+        self.rows().contains(&row) && self.columns().contains(&column)
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -221,50 +602,184 @@ pub enum ValueType {
     Int(i32),
     BigInt(i64),
     Float(f64),
+    Numeric(String),
     Bool(bool),
     Bytea(Vec<u8>),
-    Array(Vec<ValueType>),
+    Uuid(String),
+    Inet(String),
+    MacAddr(String),
+    Interval(String),
+    /// Elements of the outer dimension with the `[lower:upper]` bounds of every dimension,
+    /// which are only kept on the outer array and only when some dimension doesn't start at 1.
+    Array(Vec<ValueType>, Vec<(i32, i32)>),
+    Record(Vec<(String, ValueType)>),
+    Range(Option<Box<RangeValue>>),
     Unknown(String),
+    /// The value was received but the client failed to decode it.
+    DecodeError(String),
+}
+
+const ARRAY_SPECIAL_CHARS: &[char] = &['{', '}', ',', '"', '\\'];
+const RANGE_SPECIAL_CHARS: &[char] = &['(', ')', '[', ']', ',', '"', '\\'];
+
+/// Bounds of a non-empty range value, `None` bounds are infinite.
+#[derive(Clone, Debug)]
+pub struct RangeValue {
+    pub lower: Option<ValueType>,
+    pub upper: Option<ValueType>,
+    pub lower_inclusive: bool,
+    pub upper_inclusive: bool,
+}
+
+impl std::fmt::Display for ValueType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValueType::Null => write!(f, "NULL"),
+            ValueType::Text(text) => write!(f, "{}", text),
+            ValueType::Int(int) => write!(f, "{}", int),
+            ValueType::BigInt(big_int) => write!(f, "{}", big_int),
+            ValueType::Float(float) => write!(f, "{}", float),
+            ValueType::Numeric(numeric) => write!(f, "{}", numeric),
+            ValueType::Bool(bool) => write!(f, "{}", bool),
+            // Hex like the server shows it by default
+            ValueType::Bytea(bytes) => {
+                write!(f, "\\x")?;
+                bytes.iter().try_for_each(|byte| write!(f, "{:02x}", byte))
+            },
+            ValueType::Uuid(uuid) => write!(f, "{}", uuid),
+            ValueType::Inet(inet) => write!(f, "{}", inet),
+            ValueType::MacAddr(mac_addr) => write!(f, "{}", mac_addr),
+            ValueType::Interval(interval) => write!(f, "{}", interval),
+            ValueType::Array(value_types, bounds) => write!(
+                f,
+                "{}{{{}}}",
+                match bounds.is_empty() {
+                    true => String::new(),
+                    false => format!("{}=", bounds.iter().map(|(lower, upper)| format!("[{}:{}]", lower, upper)).collect::<String>()),
+                },
+                value_types
+                    .iter()
+                    .map(|item| item.to_array_element())
+                    .collect::<Vec<String>>()
+                    .join(",")
+            ),
+            ValueType::Record(fields) => write!(
+                f,
+                "({})",
+                fields
+                    .iter()
+                    .map(|(name, value)| format!("{}: {}", name, value))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+            ValueType::Range(None) => write!(f, "empty"),
+            ValueType::Range(Some(range)) => write!(
+                f,
+                "{}{},{}{}",
+                if range.lower_inclusive { "[" } else { "(" },
+                range.lower.as_ref().map(|v| v.to_quoted_element(RANGE_SPECIAL_CHARS)).unwrap_or_default(),
+                range.upper.as_ref().map(|v| v.to_quoted_element(RANGE_SPECIAL_CHARS)).unwrap_or_default(),
+                if range.upper_inclusive { "]" } else { ")" },
+            ),
+            ValueType::Unknown(unknown) => write!(f, "{}", unknown),
+            ValueType::DecodeError(error) => write!(f, "<decode error: {}>", error),
+        }
+    }
 }
 
 impl ValueType {
-    pub fn to_string(&self) -> String {
+    /// Renders the value as an element of a PostgreSQL array literal, quoting it when needed.
+    fn to_array_element(&self) -> String {
         match self {
-            ValueType::Null => "None".to_string(),
-            ValueType::Text(text) => text.clone(),
-            ValueType::Int(int) => int.to_string(),
-            ValueType::BigInt(big_int) => big_int.to_string(),
-            ValueType::Float(float) => float.to_string(),
-            ValueType::Bool(bool) => bool.to_string(),
-            ValueType::Bytea(items) => items
-                .iter()
-                .map(|item| item.to_string())
-                .collect::<Vec<String>>()
-                .join(", "),
-            ValueType::Array(value_types) => value_types
-                .iter()
-                .map(|item| item.to_string())
-                .collect::<Vec<String>>()
-                .join(", "),
-            ValueType::Unknown(unknown) => unknown.clone(),
+            ValueType::Null => "NULL".to_string(),
+            ValueType::Array(..) => self.to_string(),
+            _ => {
+                let value = self.to_quoted_element(ARRAY_SPECIAL_CHARS);
+                if value.eq_ignore_ascii_case("NULL") {
+                    format!("\"{}\"", value)
+                } else {
+                    value
+                }
+            }
+        }
+    }
+
+    /// Renders the value quoted like PostgreSQL does for array elements and range bounds.
+    fn to_quoted_element(&self, special_chars: &[char]) -> String {
+        let value = self.to_string();
+        let needs_quotes = value.is_empty()
+            || value.chars().any(|c| special_chars.contains(&c) || c.is_whitespace());
+
+        if needs_quotes {
+            format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+        } else {
+            value
         }
     }
 }
 
+/// A statement of the script run from a SQL page.
+#[derive(Clone)]
+pub struct SQLQueryStatement {
+    pub code: String,
+    pub sql_query_execution_status: Arc<Mutex<SQLQueryExecutionStatusType>>,
+    pub cursor: Arc<Mutex<Option<crate::database::QueryCursor>>>,
+    /// Cancelling the statement was asked for, so the server cancelling it isn't an error.
+    pub cancel_requested: Arc<AtomicBool>,
+    /// Byte range of the statement in the page code, `code` ends with it.
+    pub source_range: Option<std::ops::Range<usize>>,
+    /// The editor cursor was moved to the error of the statement.
+    pub error_located: bool,
+}
+
 #[derive(Clone)]
 pub struct SQLQueryPage {
     pub database: crate::database::Database,
     pub code: String,
     pub code_file_path: Option<String>,
-    pub sql_query_execution_status: Option<Arc<Mutex<SQLQueryExecutionStatusType>>>,
+    pub statements: Vec<SQLQueryStatement>,
+    /// Statement whose result is shown.
+    pub selected_statement: usize,
     pub output_is_empty: bool,
     pub update_page_index: Option<u32>,
+    pub fetch_action: Option<FetchAction>,
+    /// Selected characters of the editor, empty at the cursor position.
+    pub code_selection: Option<std::ops::Range<usize>>,
+    /// Byte range of the code that was last run and when, to highlight it for a moment.
+    pub executed_range: Option<(std::ops::Range<usize>, std::time::Instant)>,
+    /// Overrides the timeouts of the server for queries from this page.
+    pub timeouts: Option<crate::database::QueryTimeouts>,
+    /// Dedicated connection the page queries run in.
+    pub session: crate::database::Session,
+    /// Last values given to the placeholders of the page queries.
+    pub parameter_values: crate::database::ParameterValues,
+    /// Shows plans in the result grid instead of a tree.
+    pub plan_as_table: bool,
+    /// Byte offset of the character an error points at, underlined in the editor.
+    pub error_position: Option<usize>,
+    /// Result cells selected for copying.
+    pub grid_selection: Option<GridSelection>,
+    /// The pointer is held down over the result, extending the selection.
+    pub selecting_cells: bool,
+    /// Copies the column names along with the selected cells.
+    pub copy_headers: bool,
+}
+
+/// Page watching notifications sent with `NOTIFY` to channels of a database.
+#[derive(Clone)]
+pub struct ListenerPage {
+    pub database: crate::database::Database,
+    pub listener: crate::database::listener::Listener,
+    pub channel_field: String,
+    pub notify_channel_field: String,
+    pub notify_payload_field: String,
 }
 
 #[derive(Clone)]
 pub enum PageType {
     Welcome,
-    SQLQuery(SQLQueryPage),
+    SQLQuery(Box<SQLQueryPage>),
+    Listener(Box<ListenerPage>),
 }
 
 #[derive(Clone)]
@@ -308,5 +823,7 @@ pub enum Action {
 pub enum SelectFileDialogAction {
     SaveFile,
     OpenFile,
-    ExportToCsv,
+    ExportResult,
+    ImportCsv,
+    ExportCopy,
 }
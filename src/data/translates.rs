@@ -171,10 +171,10 @@ impl Translator {
         }
     }
 
-    pub fn export_to_csv(&self) -> String {
+    pub fn export_results(&self) -> String {
         match self.language {
-            Language::English => "Export to CSV".to_string(),
-            Language::Russian => "Экспорт в CSV".to_string(),
+            Language::English => "Export results…".to_string(),
+            Language::Russian => "Экспорт результатов…".to_string(),
         }
     }
 
@@ -402,10 +402,10 @@ impl Translator {
         }
     }
 
-    pub fn click_to_copy(&self) -> String {
+    pub fn cell_hint(&self) -> String {
         match self.language {
-            Language::English => "Click to copy".to_string(),
-            Language::Russian => "Нажмите, чтобы скопировать".to_string(),
+            Language::English => "Double-click to open, drag or shift-click to select".to_string(),
+            Language::Russian => "Двойной щелчок — открыть, перетаскивание или Shift+щелчок — выделить".to_string(),
         }
     }
 
@@ -556,4 +556,829 @@ impl Translator {
         }
     }
 
+    pub fn time_zone(&self) -> String {
+        match self.language {
+            Language::English => "Time zone".to_string(),
+            Language::Russian => "Часовой пояс".to_string(),
+        }
+    }
+
+    pub fn utc(&self) -> String {
+        match self.language {
+            Language::English => "UTC".to_string(),
+            Language::Russian => "UTC".to_string(),
+        }
+    }
+
+    pub fn local_time_zone(&self) -> String {
+        match self.language {
+            Language::English => "Local".to_string(),
+            Language::Russian => "Локальный".to_string(),
+        }
+    }
+
+    pub fn session_time_zone(&self) -> String {
+        match self.language {
+            Language::English => "Session (TimeZone)".to_string(),
+            Language::Russian => "Сессии (TimeZone)".to_string(),
+        }
+    }
+
+    pub fn csv_null(&self) -> String {
+        match self.language {
+            Language::English => "NULL in CSV".to_string(),
+            Language::Russian => "NULL в CSV".to_string(),
+        }
+    }
+
+    pub fn empty_field(&self) -> String {
+        match self.language {
+            Language::English => "Empty field".to_string(),
+            Language::Russian => "Пустое поле".to_string(),
+        }
+    }
+
+    pub fn decode_error(&self) -> String {
+        match self.language {
+            Language::English => "Decode error".to_string(),
+            Language::Russian => "Ошибка декодирования".to_string(),
+        }
+    }
+
+    pub fn max_rows(&self) -> String {
+        match self.language {
+            Language::English => "Row limit".to_string(),
+            Language::Russian => "Лимит строк".to_string(),
+        }
+    }
+
+    pub fn fetching(&self) -> String {
+        match self.language {
+            Language::English => "Fetching...".to_string(),
+            Language::Russian => "Загрузка...".to_string(),
+        }
+    }
+
+    pub fn fetch_more(&self, rows: impl ToString) -> String {
+        match self.language {
+            Language::English => format!("Fetch {} more", rows.to_string()),
+            Language::Russian => format!("Загрузить ещё {}", rows.to_string()),
+        }
+    }
+
+    pub fn fetch_all(&self) -> String {
+        match self.language {
+            Language::English => "Fetch all".to_string(),
+            Language::Russian => "Загрузить все".to_string(),
+        }
+    }
+
+    pub fn cancel_shift_f5(&self) -> String {
+        match self.language {
+            Language::English => "Cancel (Shift+F5)".to_string(),
+            Language::Russian => "Отменить (Shift+F5)".to_string(),
+        }
+    }
+
+    pub fn cancelled(&self) -> String {
+        match self.language {
+            Language::English => "Cancelled".to_string(),
+            Language::Russian => "Отменено".to_string(),
+        }
+    }
+
+    pub fn statement_timeout(&self) -> String {
+        match self.language {
+            Language::English => "Statement timeout, s".to_string(),
+            Language::Russian => "Таймаут запроса, с".to_string(),
+        }
+    }
+
+    pub fn lock_timeout(&self) -> String {
+        match self.language {
+            Language::English => "Lock timeout, s".to_string(),
+            Language::Russian => "Таймаут блокировки, с".to_string(),
+        }
+    }
+
+    pub fn incorrect_timeout_value(&self) -> String {
+        match self.language {
+            Language::English => "Timeouts must be whole numbers of seconds".to_string(),
+            Language::Russian => "Таймауты должны быть целым числом секунд".to_string(),
+        }
+    }
+
+    pub fn timeouts(&self) -> String {
+        match self.language {
+            Language::English => "Timeouts".to_string(),
+            Language::Russian => "Таймауты".to_string(),
+        }
+    }
+
+    pub fn override_server_timeouts(&self) -> String {
+        match self.language {
+            Language::English => "Override server timeouts".to_string(),
+            Language::Russian => "Переопределить таймауты сервера".to_string(),
+        }
+    }
+
+    pub fn statement_timed_out(&self, seconds: impl ToString) -> String {
+        match self.language {
+            Language::English => format!("Query timed out after {} s", seconds.to_string()),
+            Language::Russian => format!("Превышено время выполнения запроса: {} с", seconds.to_string()),
+        }
+    }
+
+    pub fn lock_timed_out(&self, seconds: impl ToString) -> String {
+        match self.language {
+            Language::English => format!("Lock wait timed out after {} s", seconds.to_string()),
+            Language::Russian => format!("Превышено время ожидания блокировки: {} с", seconds.to_string()),
+        }
+    }
+
+    pub fn pending(&self) -> String {
+        match self.language {
+            Language::English => "Waiting for previous statements...".to_string(),
+            Language::Russian => "Ожидание предыдущих запросов...".to_string(),
+        }
+    }
+
+    pub fn skipped(&self) -> String {
+        match self.language {
+            Language::English => "Not run because a previous statement failed or was cancelled".to_string(),
+            Language::Russian => "Не выполнено: предыдущий запрос завершился ошибкой или был отменён".to_string(),
+        }
+    }
+
+    pub fn rows_affected(&self, rows: impl ToString) -> String {
+        match self.language {
+            Language::English => format!("Rows affected: {}", rows.to_string()),
+            Language::Russian => format!("Затронуто строк: {}", rows.to_string()),
+        }
+    }
+
+    pub fn run_statement_ctrl_enter(&self) -> String {
+        match self.language {
+            Language::English => "Run statement (Ctrl+Enter)".to_string(),
+            Language::Russian => "Выполнить запрос (Ctrl+Enter)".to_string(),
+        }
+    }
+
+    pub fn begin(&self) -> String {
+        match self.language {
+            Language::English => "Begin".to_string(),
+            Language::Russian => "Начать".to_string(),
+        }
+    }
+
+    pub fn commit(&self) -> String {
+        match self.language {
+            Language::English => "Commit".to_string(),
+            Language::Russian => "Зафиксировать".to_string(),
+        }
+    }
+
+    pub fn rollback(&self) -> String {
+        match self.language {
+            Language::English => "Rollback".to_string(),
+            Language::Russian => "Откатить".to_string(),
+        }
+    }
+
+    pub fn transaction_idle(&self) -> String {
+        match self.language {
+            Language::English => "No transaction".to_string(),
+            Language::Russian => "Нет транзакции".to_string(),
+        }
+    }
+
+    pub fn transaction_open(&self) -> String {
+        match self.language {
+            Language::English => "Transaction open".to_string(),
+            Language::Russian => "Транзакция открыта".to_string(),
+        }
+    }
+
+    pub fn transaction_aborted(&self) -> String {
+        match self.language {
+            Language::English => "Transaction aborted".to_string(),
+            Language::Russian => "Транзакция прервана".to_string(),
+        }
+    }
+
+    pub fn transaction_aborted_hint(&self) -> String {
+        match self.language {
+            Language::English => "A statement failed, commands are ignored until the transaction is rolled back".to_string(),
+            Language::Russian => "Запрос завершился ошибкой, команды игнорируются до отката транзакции".to_string(),
+        }
+    }
+
+    pub fn close_page(&self) -> String {
+        match self.language {
+            Language::English => "Close page".to_string(),
+            Language::Russian => "Закрыть страницу".to_string(),
+        }
+    }
+
+    pub fn uncommitted_transaction_confirmation(&self, title: &str) -> String {
+        match self.language {
+            Language::English => format!("\"{}\" has an uncommitted transaction, closing the page will roll it back. Close anyway?", title),
+            Language::Russian => format!("На странице \"{}\" есть незафиксированная транзакция, при закрытии она будет отменена. Всё равно закрыть?", title),
+        }
+    }
+
+    pub fn backend_pid(&self, pid: i32) -> String {
+        match self.language {
+            Language::English => format!("PID {}", pid),
+            Language::Russian => format!("PID {}", pid),
+        }
+    }
+
+    pub fn not_connected(&self) -> String {
+        match self.language {
+            Language::English => "Not connected".to_string(),
+            Language::Russian => "Не подключено".to_string(),
+        }
+    }
+
+    pub fn disconnected(&self) -> String {
+        match self.language {
+            Language::English => "Disconnected".to_string(),
+            Language::Russian => "Отключено".to_string(),
+        }
+    }
+
+    pub fn reconnect(&self) -> String {
+        match self.language {
+            Language::English => "Reconnect".to_string(),
+            Language::Russian => "Переподключиться".to_string(),
+        }
+    }
+
+    pub fn reconnect_in_transaction(&self) -> String {
+        match self.language {
+            Language::English => "Commit or roll back the transaction first".to_string(),
+            Language::Russian => "Сначала зафиксируйте или откатите транзакцию".to_string(),
+        }
+    }
+
+    pub fn parameters(&self) -> String {
+        match self.language {
+            Language::English => "Parameters".to_string(),
+            Language::Russian => "Параметры".to_string(),
+        }
+    }
+
+    pub fn run(&self) -> String {
+        match self.language {
+            Language::English => "Run".to_string(),
+            Language::Russian => "Выполнить".to_string(),
+        }
+    }
+
+    pub fn explain(&self) -> String {
+        match self.language {
+            Language::English => "Explain".to_string(),
+            Language::Russian => "План".to_string(),
+        }
+    }
+
+    pub fn explain_analyze(&self) -> String {
+        match self.language {
+            Language::English => "Explain Analyze".to_string(),
+            Language::Russian => "План с выполнением".to_string(),
+        }
+    }
+
+    pub fn explain_analyze_hint(&self) -> String {
+        match self.language {
+            Language::English => "Runs the statement to measure it, the changes it makes are kept".to_string(),
+            Language::Russian => "Выполняет запрос для замеров, внесённые им изменения сохраняются".to_string(),
+        }
+    }
+
+    pub fn show_as_table(&self) -> String {
+        match self.language {
+            Language::English => "Show as table".to_string(),
+            Language::Russian => "Показать таблицей".to_string(),
+        }
+    }
+
+    pub fn planning_time(&self, time: impl ToString) -> String {
+        match self.language {
+            Language::English => format!("Planning: {} ms", time.to_string()),
+            Language::Russian => format!("Планирование: {} ms", time.to_string()),
+        }
+    }
+
+    pub fn execution_time(&self, time: impl ToString) -> String {
+        match self.language {
+            Language::English => format!("Execution: {} ms", time.to_string()),
+            Language::Russian => format!("Выполнение: {} ms", time.to_string()),
+        }
+    }
+
+    pub fn plan_cost(&self, startup_cost: impl ToString, total_cost: impl ToString, rows: impl ToString) -> String {
+        match self.language {
+            Language::English => format!("Cost: {}..{}, rows: {}", startup_cost.to_string(), total_cost.to_string(), rows.to_string()),
+            Language::Russian => format!("Стоимость: {}..{}, строки: {}", startup_cost.to_string(), total_cost.to_string(), rows.to_string()),
+        }
+    }
+
+    pub fn plan_actual(&self, startup_time: impl ToString, total_time: impl ToString, rows: impl ToString, loops: impl ToString) -> String {
+        match self.language {
+            Language::English => format!("Actual: {}..{} ms, rows: {}, loops: {}", startup_time.to_string(), total_time.to_string(), rows.to_string(), loops.to_string()),
+            Language::Russian => format!("Факт: {}..{} ms, строки: {}, циклы: {}", startup_time.to_string(), total_time.to_string(), rows.to_string(), loops.to_string()),
+        }
+    }
+
+    pub fn never_executed(&self) -> String {
+        match self.language {
+            Language::English => "Never executed".to_string(),
+            Language::Russian => "Не выполнялся".to_string(),
+        }
+    }
+
+    pub fn rows_misestimate(&self, factor: impl ToString) -> String {
+        match self.language {
+            Language::English => format!("Rows misestimated {} times", factor.to_string()),
+            Language::Russian => format!("Строки оценены с ошибкой в {} раз", factor.to_string()),
+        }
+    }
+
+    pub fn plan_buffers(&self, hit: impl ToString, read: impl ToString, dirtied: impl ToString, written: impl ToString) -> String {
+        match self.language {
+            Language::English => format!("Buffers: shared hit {}, read {}, dirtied {}, written {}", hit.to_string(), read.to_string(), dirtied.to_string(), written.to_string()),
+            Language::Russian => format!("Буферы: shared hit {}, read {}, dirtied {}, written {}", hit.to_string(), read.to_string(), dirtied.to_string(), written.to_string()),
+        }
+    }
+
+    pub fn plan_temp_buffers(&self, read: impl ToString, written: impl ToString) -> String {
+        match self.language {
+            Language::English => format!("Temp: read {}, written {}", read.to_string(), written.to_string()),
+            Language::Russian => format!("Временные: read {}, written {}", read.to_string(), written.to_string()),
+        }
+    }
+
+    pub fn trigger_time(&self, name: impl ToString, time: impl ToString) -> String {
+        match self.language {
+            Language::English => format!("Trigger {}: {} ms", name.to_string(), time.to_string()),
+            Language::Russian => format!("Триггер {}: {} ms", name.to_string(), time.to_string()),
+        }
+    }
+
+    pub fn error_detail(&self) -> String {
+        match self.language {
+            Language::English => "Detail".to_string(),
+            Language::Russian => "Подробности".to_string(),
+        }
+    }
+
+    pub fn error_hint(&self) -> String {
+        match self.language {
+            Language::English => "Hint".to_string(),
+            Language::Russian => "Подсказка".to_string(),
+        }
+    }
+
+    pub fn error_position(&self) -> String {
+        match self.language {
+            Language::English => "Position".to_string(),
+            Language::Russian => "Позиция".to_string(),
+        }
+    }
+
+    pub fn error_internal_query(&self) -> String {
+        match self.language {
+            Language::English => "Internal query".to_string(),
+            Language::Russian => "Внутренний запрос".to_string(),
+        }
+    }
+
+    pub fn error_where(&self) -> String {
+        match self.language {
+            Language::English => "Context".to_string(),
+            Language::Russian => "Контекст".to_string(),
+        }
+    }
+
+    pub fn error_schema(&self) -> String {
+        match self.language {
+            Language::English => "Schema".to_string(),
+            Language::Russian => "Схема".to_string(),
+        }
+    }
+
+    pub fn error_table(&self) -> String {
+        match self.language {
+            Language::English => "Table".to_string(),
+            Language::Russian => "Таблица".to_string(),
+        }
+    }
+
+    pub fn error_column(&self) -> String {
+        match self.language {
+            Language::English => "Column".to_string(),
+            Language::Russian => "Столбец".to_string(),
+        }
+    }
+
+    pub fn error_data_type(&self) -> String {
+        match self.language {
+            Language::English => "Data type".to_string(),
+            Language::Russian => "Тип данных".to_string(),
+        }
+    }
+
+    pub fn error_constraint(&self) -> String {
+        match self.language {
+            Language::English => "Constraint".to_string(),
+            Language::Russian => "Ограничение".to_string(),
+        }
+    }
+
+    pub fn line_column(&self, line: impl ToString, column: impl ToString) -> String {
+        match self.language {
+            Language::English => format!("Line {}, column {}", line.to_string(), column.to_string()),
+            Language::Russian => format!("Строка {}, столбец {}", line.to_string(), column.to_string()),
+        }
+    }
+
+    pub fn go_to_error(&self) -> String {
+        match self.language {
+            Language::English => "Click to move the cursor to the error".to_string(),
+            Language::Russian => "Нажмите, чтобы перейти к ошибке".to_string(),
+        }
+    }
+
+    pub fn listen_notify(&self) -> String {
+        match self.language {
+            Language::English => "Listen / Notify".to_string(),
+            Language::Russian => "Listen / Notify".to_string(),
+        }
+    }
+
+    pub fn channel(&self) -> String {
+        match self.language {
+            Language::English => "Channel".to_string(),
+            Language::Russian => "Канал".to_string(),
+        }
+    }
+
+    pub fn listen(&self) -> String {
+        match self.language {
+            Language::English => "Listen".to_string(),
+            Language::Russian => "Слушать".to_string(),
+        }
+    }
+
+    pub fn unlisten_hint(&self) -> String {
+        match self.language {
+            Language::English => "Click to stop listening".to_string(),
+            Language::Russian => "Нажмите, чтобы перестать слушать".to_string(),
+        }
+    }
+
+    pub fn not_listening(&self) -> String {
+        match self.language {
+            Language::English => "Not listening to any channel".to_string(),
+            Language::Russian => "Каналы не прослушиваются".to_string(),
+        }
+    }
+
+    pub fn connecting(&self) -> String {
+        match self.language {
+            Language::English => "Connecting...".to_string(),
+            Language::Russian => "Подключение...".to_string(),
+        }
+    }
+
+    pub fn payload(&self) -> String {
+        match self.language {
+            Language::English => "Payload".to_string(),
+            Language::Russian => "Содержимое".to_string(),
+        }
+    }
+
+    pub fn send_notify(&self) -> String {
+        match self.language {
+            Language::English => "Send NOTIFY".to_string(),
+            Language::Russian => "Отправить NOTIFY".to_string(),
+        }
+    }
+
+    pub fn clear_notifications(&self) -> String {
+        match self.language {
+            Language::English => "Clear".to_string(),
+            Language::Russian => "Очистить".to_string(),
+        }
+    }
+
+    pub fn no_notifications(&self) -> String {
+        match self.language {
+            Language::English => "No notifications yet".to_string(),
+            Language::Russian => "Уведомлений пока нет".to_string(),
+        }
+    }
+
+    pub fn sender_pid(&self) -> String {
+        match self.language {
+            Language::English => "Sender PID".to_string(),
+            Language::Russian => "PID отправителя".to_string(),
+        }
+    }
+
+    pub fn notifications_count(&self, count: impl ToString) -> String {
+        match self.language {
+            Language::English => format!("Notifications: {}", count.to_string()),
+            Language::Russian => format!("Уведомления: {}", count.to_string()),
+        }
+    }
+
+    pub fn listening(&self) -> String {
+        match self.language {
+            Language::English => "Listening".to_string(),
+            Language::Russian => "Прослушивание".to_string(),
+        }
+    }
+
+    pub fn received_at(&self) -> String {
+        match self.language {
+            Language::English => "Received".to_string(),
+            Language::Russian => "Получено".to_string(),
+        }
+    }
+
+    pub fn import_data(&self) -> String {
+        match self.language {
+            Language::English => "Import data…".to_string(),
+            Language::Russian => "Импорт данных…".to_string(),
+        }
+    }
+
+    pub fn choose_file(&self) -> String {
+        match self.language {
+            Language::English => "Choose file".to_string(),
+            Language::Russian => "Выбрать файл".to_string(),
+        }
+    }
+
+    pub fn delimiter(&self) -> String {
+        match self.language {
+            Language::English => "Delimiter".to_string(),
+            Language::Russian => "Разделитель".to_string(),
+        }
+    }
+
+    pub fn quote(&self) -> String {
+        match self.language {
+            Language::English => "Quote".to_string(),
+            Language::Russian => "Кавычка".to_string(),
+        }
+    }
+
+    pub fn encoding(&self) -> String {
+        match self.language {
+            Language::English => "Encoding".to_string(),
+            Language::Russian => "Кодировка".to_string(),
+        }
+    }
+
+    pub fn header_row(&self) -> String {
+        match self.language {
+            Language::English => "First row is a header".to_string(),
+            Language::Russian => "Первая строка — заголовок".to_string(),
+        }
+    }
+
+    pub fn preview(&self) -> String {
+        match self.language {
+            Language::English => "Preview".to_string(),
+            Language::Russian => "Предпросмотр".to_string(),
+        }
+    }
+
+    pub fn column_mapping(&self) -> String {
+        match self.language {
+            Language::English => "Columns".to_string(),
+            Language::Russian => "Столбцы".to_string(),
+        }
+    }
+
+    pub fn skip_column(&self) -> String {
+        match self.language {
+            Language::English => "Skip".to_string(),
+            Language::Russian => "Пропустить".to_string(),
+        }
+    }
+
+    pub fn import(&self) -> String {
+        match self.language {
+            Language::English => "Import".to_string(),
+            Language::Russian => "Импортировать".to_string(),
+        }
+    }
+
+    pub fn cancel(&self) -> String {
+        match self.language {
+            Language::English => "Cancel".to_string(),
+            Language::Russian => "Отмена".to_string(),
+        }
+    }
+
+    pub fn import_data_into(&self, table: impl ToString) -> String {
+        match self.language {
+            Language::English => format!("Import data into {}", table.to_string()),
+            Language::Russian => format!("Импорт данных в {}", table.to_string()),
+        }
+    }
+
+    pub fn imported_rows(&self, rows: impl ToString) -> String {
+        match self.language {
+            Language::English => format!("Imported rows: {}", rows.to_string()),
+            Language::Russian => format!("Импортировано строк: {}", rows.to_string()),
+        }
+    }
+
+    pub fn export_table(&self) -> String {
+        match self.language {
+            Language::English => "Export table…".to_string(),
+            Language::Russian => "Экспорт таблицы…".to_string(),
+        }
+    }
+
+    pub fn export_full_query(&self) -> String {
+        match self.language {
+            Language::English => "Export full query…".to_string(),
+            Language::Russian => "Экспорт всего запроса…".to_string(),
+        }
+    }
+
+    pub fn format(&self) -> String {
+        match self.language {
+            Language::English => "Format".to_string(),
+            Language::Russian => "Формат".to_string(),
+        }
+    }
+
+    pub fn export(&self) -> String {
+        match self.language {
+            Language::English => "Export".to_string(),
+            Language::Russian => "Экспортировать".to_string(),
+        }
+    }
+
+    pub fn export_table_name(&self, table: impl ToString) -> String {
+        match self.language {
+            Language::English => format!("Export table {}", table.to_string()),
+            Language::Russian => format!("Экспорт таблицы {}", table.to_string()),
+        }
+    }
+
+    pub fn exported_rows(&self, rows: impl ToString) -> String {
+        match self.language {
+            Language::English => format!("Exported rows: {}", rows.to_string()),
+            Language::Russian => format!("Экспортировано строк: {}", rows.to_string()),
+        }
+    }
+
+    pub fn target_table(&self) -> String {
+        match self.language {
+            Language::English => "Target table".to_string(),
+            Language::Russian => "Целевая таблица".to_string(),
+        }
+    }
+
+    pub fn rows_per_insert(&self) -> String {
+        match self.language {
+            Language::English => "Rows per INSERT".to_string(),
+            Language::Russian => "Строк в INSERT".to_string(),
+        }
+    }
+
+    pub fn copy_ctrl_c(&self) -> String {
+        match self.language {
+            Language::English => "Copy (Ctrl+C)".to_string(),
+            Language::Russian => "Копировать (Ctrl+C)".to_string(),
+        }
+    }
+
+    pub fn copy_as(&self) -> String {
+        match self.language {
+            Language::English => "Copy as".to_string(),
+            Language::Russian => "Копировать как".to_string(),
+        }
+    }
+
+    pub fn include_headers(&self) -> String {
+        match self.language {
+            Language::English => "Include headers".to_string(),
+            Language::Russian => "Включать заголовки".to_string(),
+        }
+    }
+
+    pub fn select_all_ctrl_a(&self) -> String {
+        match self.language {
+            Language::English => "Select all (Ctrl+A)".to_string(),
+            Language::Russian => "Выделить всё (Ctrl+A)".to_string(),
+        }
+    }
+
+    pub fn ssl_mode(&self) -> String {
+        match self.language {
+            Language::English => "SSL mode".to_string(),
+            Language::Russian => "Режим SSL".to_string(),
+        }
+    }
+
+    pub fn ssl_root_cert(&self) -> String {
+        match self.language {
+            Language::English => "CA certificate".to_string(),
+            Language::Russian => "Сертификат CA".to_string(),
+        }
+    }
+
+    pub fn ssl_client_cert(&self) -> String {
+        match self.language {
+            Language::English => "Client certificate".to_string(),
+            Language::Russian => "Сертификат клиента".to_string(),
+        }
+    }
+
+    pub fn ssl_client_key(&self) -> String {
+        match self.language {
+            Language::English => "Client key".to_string(),
+            Language::Russian => "Ключ клиента".to_string(),
+        }
+    }
+
+    pub fn client_cert_and_key_are_required_together(&self) -> String {
+        match self.language {
+            Language::English => "Client certificate and key must be set together".to_string(),
+            Language::Russian => "Сертификат и ключ клиента задаются вместе".to_string(),
+        }
+    }
+
+    pub fn tls_connection(&self, version: impl ToString, cipher: impl ToString) -> String {
+        match self.language {
+            Language::English => format!("Encrypted with {} ({})", version.to_string(), cipher.to_string()),
+            Language::Russian => format!("Зашифровано: {} ({})", version.to_string(), cipher.to_string()),
+        }
+    }
+
+    pub fn connection_string(&self) -> String {
+        match self.language {
+            Language::English => "Connection string".to_string(),
+            Language::Russian => "Строка подключения".to_string(),
+        }
+    }
+
+    pub fn server_address_hint(&self) -> String {
+        match self.language {
+            Language::English => "Host, IP address or socket directory".to_string(),
+            Language::Russian => "Хост, IP-адрес или каталог сокета".to_string(),
+        }
+    }
+
+    pub fn cursor_transaction_open(&self, seconds_left: u64) -> String {
+        match self.language {
+            Language::English => format!("Transaction held for the cursor ({} s left)", seconds_left),
+            Language::Russian => format!("Транзакция удерживается для курсора (осталось {} с)", seconds_left),
+        }
+    }
+
+    pub fn cursor_transaction_hint(&self, timeout: u64) -> String {
+        match self.language {
+            Language::English => format!("Rows left to fetch are read from a cursor in a transaction, which is committed if the cursor isn't read for {} s", timeout),
+            Language::Russian => format!("Оставшиеся строки читаются из курсора в транзакции, которая фиксируется, если курсор не читается {} с", timeout),
+        }
+    }
+
+    pub fn cursor_expired(&self) -> String {
+        match self.language {
+            Language::English => "Cursor closed, run the query again to fetch more".to_string(),
+            Language::Russian => "Курсор закрыт, выполните запрос снова, чтобы получить больше строк".to_string(),
+        }
+    }
+
+    pub fn cancel_not_connected(&self) -> String {
+        match self.language {
+            Language::English => "The session is still connecting, there is nothing to cancel yet".to_string(),
+            Language::Russian => "Сессия ещё подключается, отменять пока нечего".to_string(),
+        }
+    }
+
+    pub fn transaction_rolled_back(&self) -> String {
+        match self.language {
+            Language::English => "Disconnected, the transaction was rolled back".to_string(),
+            Language::Russian => "Отключено, транзакция отменена".to_string(),
+        }
+    }
+
+    pub fn ssl_root_cert_hint(&self) -> String {
+        match self.language {
+            Language::English => "Built-in Mozilla root certificates".to_string(),
+            Language::Russian => "Встроенные корневые сертификаты Mozilla".to_string(),
+        }
+    }
 }
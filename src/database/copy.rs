@@ -0,0 +1,625 @@
+use futures_util::TryStreamExt;
+use sqlx::Row;
+use sqlx_postgres::{PgConnection, PgDatabaseError, PgPoolCopyExt};
+
+use std::fmt;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use crate::data::structs::Table;
+
+use super::session::{Session, TransactionStatus};
+use super::{Database, ParameterValues};
+
+/// Encodings files can be read in, the server converts them to the encoding of the database.
+pub const ENCODINGS: &[&str] = &["UTF8", "WIN1251", "KOI8R", "WIN1252", "LATIN1", "LATIN9"];
+
+/// Data sent to the server in one message.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// How a CSV file is read.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CsvOptions {
+    pub delimiter: u8,
+    pub quote: u8,
+    /// The first line holds column names rather than data.
+    pub header: bool,
+    /// Unquoted value read as NULL.
+    pub null: String,
+    /// One of [`ENCODINGS`].
+    pub encoding: String,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self { delimiter: b',', quote: b'"', header: true, null: String::new(), encoding: String::from("UTF8") }
+    }
+}
+
+impl CsvOptions {
+    fn reader<R: Read>(&self, reader: R) -> csv::Reader<R> {
+        csv::ReaderBuilder::new()
+            .delimiter(self.delimiter)
+            .quote(self.quote)
+            .has_headers(false)
+            .flexible(true)
+            .from_reader(reader)
+    }
+}
+
+/// Reads CSV records keeping whether each value was quoted, since like `COPY` only unquoted values can be NULL.
+/// Quotes are read anywhere in a value and a doubled quote inside of them is a quote, empty lines are skipped.
+struct CsvRecords<R> {
+    reader: R,
+    delimiter: u8,
+    quote: u8,
+    /// Bytes read so far.
+    position: u64,
+}
+
+impl<R: BufRead> CsvRecords<R> {
+    fn new(reader: R, options: &CsvOptions) -> Self {
+        Self { reader, delimiter: options.delimiter, quote: options.quote, position: 0 }
+    }
+
+    fn peek(&mut self) -> std::io::Result<Option<u8>> {
+        Ok(self.reader.fill_buf()?.first().copied())
+    }
+
+    fn next_byte(&mut self) -> std::io::Result<Option<u8>> {
+        let byte = self.peek()?;
+        if byte.is_some() {
+            self.reader.consume(1);
+            self.position += 1;
+        }
+
+        Ok(byte)
+    }
+
+    /// Reads the values of the next record with whether they were quoted, returns `false` at the end of the file.
+    fn read(&mut self, values: &mut Vec<(Vec<u8>, bool)>) -> Result<bool, String> {
+        values.clear();
+
+        let mut value = Vec::new();
+        let mut quoted = false;
+        let mut in_quotes = false;
+        let mut is_empty_line = true;
+
+        loop {
+            let Some(byte) = self.next_byte().map_err(|e| e.to_string())? else {
+                if in_quotes {
+                    return Err(String::from("Unterminated quoted value at the end of the file"));
+                }
+                if !is_empty_line {
+                    values.push((value, quoted));
+                }
+
+                return Ok(!is_empty_line);
+            };
+
+            if in_quotes {
+                if byte != self.quote {
+                    value.push(byte);
+                } else if self.peek().map_err(|e| e.to_string())? == Some(self.quote) {
+                    self.next_byte().map_err(|e| e.to_string())?;
+                    value.push(byte);
+                } else {
+                    in_quotes = false;
+                }
+                continue;
+            }
+
+            match byte {
+                b'\n' | b'\r' => {
+                    if byte == b'\r' && self.peek().map_err(|e| e.to_string())? == Some(b'\n') {
+                        self.next_byte().map_err(|e| e.to_string())?;
+                    }
+                    if !is_empty_line {
+                        values.push((value, quoted));
+                        return Ok(true);
+                    }
+                },
+                byte if byte == self.delimiter => {
+                    values.push((std::mem::take(&mut value), quoted));
+                    quoted = false;
+                    is_empty_line = false;
+                },
+                byte if byte == self.quote => {
+                    in_quotes = true;
+                    quoted = true;
+                    is_empty_line = false;
+                },
+                byte => {
+                    value.push(byte);
+                    is_empty_line = false;
+                },
+            }
+        }
+    }
+}
+
+/// First rows of a CSV file, values are decoded as UTF-8 whatever the encoding.
+#[derive(Clone, Debug)]
+pub struct CsvPreview {
+    /// Names from the header, or `column1`, `column2` and so on without it.
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+/// Reads the column names and up to `rows` rows of the file.
+pub fn preview_csv(path: &Path, options: &CsvOptions, rows: usize) -> Result<CsvPreview, String> {
+    let file = File::open(path).map_err(|e| e.to_string())?;
+    let mut reader = options.reader(BufReader::new(file));
+
+    let mut records = reader.byte_records();
+    let header = match options.header {
+        true => records.next().transpose().map_err(|e| e.to_string())?,
+        false => None,
+    };
+
+    let rows = records
+        .take(rows)
+        .map(|record| record.map(|record| record.iter().map(|value| String::from_utf8_lossy(value).to_string()).collect()))
+        .collect::<Result<Vec<Vec<String>>, csv::Error>>()
+        .map_err(|e| e.to_string())?;
+
+    let columns = match header {
+        Some(header) => header.iter().map(|name| String::from_utf8_lossy(name).to_string()).collect(),
+        None => {
+            let count = rows.iter().map(Vec::len).max().unwrap_or_default();
+            (1..=count).map(|number| format!("column{}", number)).collect()
+        },
+    };
+
+    Ok(CsvPreview { columns, rows })
+}
+
+/// Format a table or a query is exported in.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum CopyFormat {
+    #[default]
+    Csv,
+    Tsv,
+    /// Text format of `COPY`, tab separated with backslash escapes.
+    Text,
+    /// Binary format of `COPY`, only readable by `COPY ... FROM`.
+    Binary,
+}
+
+impl CopyFormat {
+    pub const ALL: [CopyFormat; 4] = [CopyFormat::Csv, CopyFormat::Tsv, CopyFormat::Text, CopyFormat::Binary];
+
+    /// Options of `COPY` for the format, CSV files have a header and write NULL as `null`.
+    fn options(&self, null: &str) -> String {
+        let null = null.replace('\'', "''");
+
+        match self {
+            CopyFormat::Csv => format!("FORMAT csv, HEADER true, NULL '{}'", null),
+            CopyFormat::Tsv => format!("FORMAT csv, HEADER true, DELIMITER E'\\t', NULL '{}'", null),
+            CopyFormat::Text => String::from("FORMAT text"),
+            CopyFormat::Binary => String::from("FORMAT binary"),
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            CopyFormat::Csv => "csv",
+            CopyFormat::Tsv => "tsv",
+            CopyFormat::Text => "txt",
+            CopyFormat::Binary => "bin",
+        }
+    }
+}
+
+impl fmt::Display for CopyFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CopyFormat::Csv => write!(f, "CSV"),
+            CopyFormat::Tsv => write!(f, "TSV"),
+            CopyFormat::Text => write!(f, "PostgreSQL text"),
+            CopyFormat::Binary => write!(f, "PostgreSQL binary"),
+        }
+    }
+}
+
+/// What is exported.
+#[derive(Clone)]
+pub enum CopySource {
+    Table(Table),
+    /// Statement of a SQL page, run in the session of the page with the values of its placeholders.
+    Query {
+        statement: String,
+        session: Session,
+        values: ParameterValues,
+    },
+}
+
+/// Progress of a `COPY` running in the background, shared with the window that started it.
+#[derive(Clone, Default)]
+pub struct CopyProgress {
+    state: Arc<Mutex<CopyState>>,
+    cancelled: Arc<AtomicBool>,
+}
+
+#[derive(Default)]
+struct CopyState {
+    bytes: u64,
+    total_bytes: Option<u64>,
+    rows: u64,
+    result: Option<Result<u64, String>>,
+}
+
+impl CopyProgress {
+    /// Bytes of the file read or written so far.
+    pub fn bytes(&self) -> u64 {
+        self.state.lock().unwrap().bytes
+    }
+
+    /// Size of the file, known beforehand for imports only.
+    pub fn total_bytes(&self) -> Option<u64> {
+        self.state.lock().unwrap().total_bytes
+    }
+
+    /// Rows sent or received so far.
+    pub fn rows(&self) -> u64 {
+        self.state.lock().unwrap().rows
+    }
+
+    /// Row count reported by the server once the copy has finished, or why it failed.
+    pub fn result(&self) -> Option<Result<u64, String>> {
+        self.state.lock().unwrap().result.clone()
+    }
+
+    /// Aborts the copy, an import is rolled back and a partly exported file removed.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    fn update(&self, bytes: u64, rows: u64) {
+        let mut state = self.state.lock().unwrap();
+        state.bytes = bytes;
+        state.rows = rows;
+    }
+
+    fn finish(&self, result: Result<u64, String>) {
+        self.state.lock().unwrap().result = Some(result);
+    }
+}
+
+/// Quotes the name as an SQL identifier.
+pub(super) fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// Name of the table qualified with its schema, the way `COPY` takes it.
+fn table_identifier(table: &Table) -> String {
+    format!("{}.{}", quote_identifier(&table.schema), quote_identifier(&table.name))
+}
+
+/// How a copy into a file failed, which tells whether the connection is still in step with the server.
+enum CopyOutError {
+    /// The server rejected the statement before sending rows, sqlx leaves the ReadyForQuery after it unread.
+    Rejected(String),
+    /// The server ended the copy and the connection is ready for the next statement.
+    Ended(String),
+    /// Reading stopped in the middle of the copy or the connection broke.
+    OutOfStep(String),
+}
+
+impl CopyOutError {
+    /// Sorts an error of the copy stream by whether it came from the server.
+    fn from_stream(error: sqlx::Error) -> Self {
+        match error {
+            sqlx::Error::Database(_) => CopyOutError::Ended(copy_error(error)),
+            error => CopyOutError::OutOfStep(copy_error(error)),
+        }
+    }
+}
+
+impl fmt::Display for CopyOutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CopyOutError::Rejected(error) | CopyOutError::Ended(error) | CopyOutError::OutOfStep(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+/// Message of a failed copy with the line and column the server stopped at.
+pub(super) fn copy_error(error: sqlx::Error) -> String {
+    match error.as_database_error().and_then(|e| e.try_downcast_ref::<PgDatabaseError>()) {
+        Some(e) => match e.r#where() {
+            Some(context) => format!("{}\n{}", e.message(), context),
+            None => e.message().to_string(),
+        },
+        None => error.to_string(),
+    }
+}
+
+impl Database {
+    /// Names of the columns of the table in their order.
+    pub async fn get_columns(&self, table: &Table) -> Result<Vec<String>, String> {
+        let rows = sqlx::query("SELECT attname::text FROM pg_attribute WHERE attrelid = format('%I.%I', $1, $2)::regclass AND attnum > 0 AND NOT attisdropped ORDER BY attnum")
+            .bind(&table.schema)
+            .bind(&table.name)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        rows.into_iter()
+            .map(|row| row.try_get(0).map_err(|e| e.to_string()))
+            .collect()
+    }
+
+    /// Streams the CSV file into the table with `COPY ... FROM STDIN` in the background of the runtime.
+    /// `columns` pairs the index of a file column with the table column it goes to, other file columns are skipped.
+    pub fn import_csv(&self, runtime: &tokio::runtime::Runtime, table: Table, columns: Vec<(usize, String)>, path: PathBuf, options: CsvOptions) -> CopyProgress {
+        let progress = CopyProgress::default();
+
+        let database = self.clone();
+        let task_progress = progress.clone();
+        runtime.spawn(async move {
+            let result = database.copy_csv(&table, &columns, &path, &options, &task_progress).await;
+            task_progress.finish(result);
+        });
+
+        progress
+    }
+
+    async fn copy_csv(&self, table: &Table, columns: &[(usize, String)], path: &Path, options: &CsvOptions, progress: &CopyProgress) -> Result<u64, String> {
+        let file = File::open(path).map_err(|e| e.to_string())?;
+        progress.state.lock().unwrap().total_bytes = file.metadata().ok().map(|metadata| metadata.len());
+
+        let statement = format!(
+            "COPY {} ({}) FROM STDIN WITH (FORMAT csv, ENCODING '{}')",
+            table_identifier(table),
+            columns.iter().map(|(_, column)| quote_identifier(column)).collect::<Vec<String>>().join(", "),
+            options.encoding.replace('\'', "''"),
+        );
+        let mut copy = self.pool.copy_in_raw(&statement).await.map_err(copy_error)?;
+
+        // Rows are written again with the default format of COPY, so the null string, the delimiter
+        // and the quote of the file don't have to be told apart by the server
+        let mut records = CsvRecords::new(BufReader::new(file), options);
+        let mut record = Vec::new();
+        let mut buffer = Vec::with_capacity(CHUNK_SIZE * 2);
+        let mut rows = 0;
+        let mut skip_header = options.header;
+
+        loop {
+            if progress.is_cancelled() {
+                copy.abort("Import cancelled").await.map_err(copy_error)?;
+                return Err(String::from("Import cancelled"));
+            }
+
+            let read = match records.read(&mut record) {
+                Ok(read) => read,
+                Err(e) => {
+                    let _ = copy.abort(e.clone()).await;
+                    return Err(e);
+                },
+            };
+
+            if read && !skip_header {
+                for (position, (index, _)) in columns.iter().enumerate() {
+                    if position > 0 {
+                        buffer.push(b',');
+                    }
+
+                    match record.get(*index) {
+                        Some((value, quoted)) if *quoted || value != options.null.as_bytes() => {
+                            buffer.push(b'"');
+                            for byte in value {
+                                if *byte == b'"' {
+                                    buffer.push(b'"');
+                                }
+                                buffer.push(*byte);
+                            }
+                            buffer.push(b'"');
+                        },
+                        // Missing values are NULL too
+                        _ => {},
+                    }
+                }
+                buffer.push(b'\n');
+                rows += 1;
+            }
+            skip_header = false;
+
+            if buffer.len() >= CHUNK_SIZE || (!read && !buffer.is_empty()) {
+                copy.send(std::mem::take(&mut buffer)).await.map_err(copy_error)?;
+                progress.update(records.position, rows);
+            }
+
+            if !read {
+                break;
+            }
+        }
+
+        copy.finish().await.map_err(copy_error)
+    }
+
+    /// Streams the rows of the table or query into the file with `COPY ... TO STDOUT` in the background of the runtime.
+    /// `null` is written for NULL in CSV and TSV files.
+    pub fn export(&self, runtime: &tokio::runtime::Runtime, source: CopySource, path: PathBuf, format: CopyFormat, null: String) -> CopyProgress {
+        let progress = CopyProgress::default();
+
+        let database = self.clone();
+        let task_progress = progress.clone();
+        runtime.spawn(async move {
+            let result = database.copy_to_file(&source, &path, format, &null, &task_progress).await;
+
+            // A file cut short is worse than none
+            if result.is_err() {
+                let _ = std::fs::remove_file(&path);
+            }
+            task_progress.finish(result);
+        });
+
+        progress
+    }
+
+    async fn copy_to_file(&self, source: &CopySource, path: &Path, format: CopyFormat, null: &str, progress: &CopyProgress) -> Result<u64, String> {
+        let copy_statement = |source: &str| format!("COPY {} TO STDOUT WITH ({})", source, format.options(null));
+
+        match source {
+            CopySource::Table(table) => {
+                let mut connection = self.pool.acquire().await.map_err(|e| e.to_string())?;
+
+                let result = self.copy_out(&mut connection, &copy_statement(&table_identifier(table)), path, format, progress, None).await;
+
+                if matches!(result, Err(CopyOutError::Rejected(_) | CopyOutError::OutOfStep(_))) {
+                    connection.close_on_drop();
+                }
+
+                result.map_err(|e| e.to_string())
+            },
+            CopySource::Query { statement, session, values } => {
+                let mut session_connection = session.connection.lock().await;
+                let connection = session.connect(self, &mut session_connection).await?;
+
+                // The cursor of the last run would keep the statement from running in its own transaction
+                session.end_cursor_transaction(connection).await?;
+
+                let in_transaction = session.transaction_status() == TransactionStatus::Open;
+                let query = Self::inline_parameters(&mut connection.connection, statement, values, in_transaction).await?;
+
+                // The statement may end with a line comment, a stopped export is cancelled on the server
+                // so that the session keeps its transaction
+                let result = self.copy_out(&mut connection.connection, &copy_statement(&format!("({}\n)", query)), path, format, progress, session.pid()).await;
+
+                match &result {
+                    Ok(_) | Err(CopyOutError::Ended(_)) => session.update_transaction_status(&mut session_connection).await,
+                    Err(CopyOutError::Rejected(_)) => {
+                        // The next exchange reads the ReadyForQuery left over in place of its first reply and fails,
+                        // then sqlx drains the rest of its replies and the connection is in step again
+                        let _ = sqlx::query("SELECT 1").persistent(false).execute(&mut connection.connection).await;
+                        session.update_transaction_status(&mut session_connection).await;
+                    },
+                    Err(e @ CopyOutError::OutOfStep(_)) => session.lose_connection(&mut session_connection, e.to_string()),
+                }
+
+                result.map_err(|e| e.to_string())
+            },
+        }
+    }
+
+    /// Runs the `COPY ... TO STDOUT` statement into the file.
+    /// With the PID of the backend, an export that is cancelled or can't be written asks the server to cancel the copy
+    /// and reads what's left of it, so that the connection stays in step, otherwise reading just stops.
+    async fn copy_out(
+        &self,
+        connection: &mut PgConnection,
+        statement: &str,
+        path: &Path,
+        format: CopyFormat,
+        progress: &CopyProgress,
+        backend_pid: Option<i32>,
+    ) -> Result<u64, CopyOutError> {
+        let mut stream = connection.copy_out_raw(statement).await.map_err(|e| CopyOutError::Rejected(copy_error(e)))?;
+
+        let mut file = File::create(path).map(BufWriter::new).map_err(|e| e.to_string());
+        let mut cancel_requested = false;
+        let mut bytes = 0;
+        // The server sends every row in a message of its own
+        let mut messages = 0;
+
+        loop {
+            if progress.is_cancelled() && file.is_ok() {
+                file = Err(String::from("Export cancelled"));
+            }
+
+            if let Err(stopped) = &file {
+                match backend_pid {
+                    Some(pid) if !cancel_requested => {
+                        self.cancel_query(pid).await.map_err(CopyOutError::OutOfStep)?;
+                        cancel_requested = true;
+                    },
+                    Some(_) => {},
+                    None => return Err(CopyOutError::OutOfStep(stopped.clone())),
+                }
+            }
+
+            let data = match stream.try_next().await {
+                Ok(Some(data)) => data,
+                Ok(None) => break,
+                Err(e) => {
+                    return Err(match (&file, CopyOutError::from_stream(e)) {
+                        // The server ended the copy on the cancel request
+                        (Err(stopped), CopyOutError::Ended(_)) => CopyOutError::Ended(stopped.clone()),
+                        (_, error) => error,
+                    });
+                },
+            };
+
+            if let Ok(writer) = &mut file {
+                match writer.write_all(&data) {
+                    Ok(()) => {
+                        bytes += data.len() as u64;
+                        messages += 1;
+                        progress.update(bytes, messages);
+                    },
+                    Err(e) => file = Err(e.to_string()),
+                }
+            }
+        }
+
+        // The copy may end before the cancel request reaches it
+        let mut file = file.map_err(CopyOutError::Ended)?;
+        file.flush().map_err(|e| CopyOutError::Ended(e.to_string()))?;
+
+        // The CSV header and the binary trailer come in messages of their own
+        let rows = match format {
+            CopyFormat::Text => messages,
+            CopyFormat::Csv | CopyFormat::Tsv | CopyFormat::Binary => messages.saturating_sub(1),
+        };
+        progress.update(bytes, rows);
+
+        Ok(rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records(csv: &str, delimiter: u8) -> Vec<Vec<(String, bool)>> {
+        let options = CsvOptions { delimiter, ..Default::default() };
+        let mut records = CsvRecords::new(csv.as_bytes(), &options);
+        let mut record = Vec::new();
+        let mut result = Vec::new();
+
+        while records.read(&mut record).unwrap() {
+            result.push(record.iter().map(|(value, quoted)| (String::from_utf8_lossy(value).to_string(), *quoted)).collect());
+        }
+
+        result
+    }
+
+    #[test]
+    fn quoted_values_are_told_from_unquoted() {
+        let value = |value: &str, quoted: bool| (value.to_string(), quoted);
+
+        assert_eq!(
+            records("a,,\"\"\r\n\n\"x\"\"y\",\"1\n2\",b\"c\"", b','),
+            [
+                vec![value("a", false), value("", false), value("", true)],
+                vec![value("x\"y", true), value("1\n2", true), value("bc", true)],
+            ],
+        );
+        assert_eq!(records("a;b,c\n", b';'), [vec![value("a", false), value("b,c", false)]]);
+        assert_eq!(records("", b','), Vec::<Vec<(String, bool)>>::new());
+    }
+
+    #[test]
+    fn unterminated_quote_fails() {
+        let mut records = CsvRecords::new("\"a,b\n".as_bytes(), &CsvOptions::default());
+        assert!(records.read(&mut Vec::new()).is_err());
+    }
+}
@@ -0,0 +1,769 @@
+use sqlx::ValueRef;
+use sqlx_postgres::{PgTypeInfo, PgTypeKind, PgValueFormat, PgValueRef};
+
+use chrono::{FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Offset, TimeDelta, TimeZone as _, Utc};
+
+use std::collections::HashMap;
+
+use crate::data::structs::{RangeValue, ValueType};
+
+const NUMERIC_POS: u16 = 0x0000;
+const NUMERIC_NEG: u16 = 0x4000;
+const NUMERIC_NAN: u16 = 0xC000;
+const NUMERIC_PINF: u16 = 0xD000;
+const NUMERIC_NINF: u16 = 0xF000;
+
+const PGSQL_AF_INET: u8 = 2;
+const PGSQL_AF_INET6: u8 = 3;
+
+const RANGE_EMPTY: u8 = 0x01;
+const RANGE_LB_INC: u8 = 0x02;
+const RANGE_UB_INC: u8 = 0x04;
+const RANGE_LB_INF: u8 = 0x08;
+const RANGE_UB_INF: u8 = 0x10;
+
+/// Type OIDs of the built-in types, see `pg_type.dat` in the PostgreSQL sources.
+pub mod oid {
+    pub const BOOL: u32 = 16;
+    pub const BYTEA: u32 = 17;
+    pub const CHAR: u32 = 18;
+    pub const NAME: u32 = 19;
+    pub const INT8: u32 = 20;
+    pub const INT2: u32 = 21;
+    pub const INT4: u32 = 23;
+    pub const TEXT: u32 = 25;
+    pub const OID: u32 = 26;
+    pub const JSON: u32 = 114;
+    pub const XML: u32 = 142;
+    pub const CIDR: u32 = 650;
+    pub const UNKNOWN: u32 = 705;
+    pub const FLOAT4: u32 = 700;
+    pub const FLOAT8: u32 = 701;
+    pub const MACADDR8: u32 = 774;
+    pub const MONEY: u32 = 790;
+    pub const MACADDR: u32 = 829;
+    pub const INET: u32 = 869;
+    pub const ACLITEM: u32 = 1033;
+    pub const BPCHAR: u32 = 1042;
+    pub const VARCHAR: u32 = 1043;
+    pub const DATE: u32 = 1082;
+    pub const TIME: u32 = 1083;
+    pub const TIMESTAMP: u32 = 1114;
+    pub const TIMESTAMPTZ: u32 = 1184;
+    pub const INTERVAL: u32 = 1186;
+    pub const TIMETZ: u32 = 1266;
+    pub const NUMERIC: u32 = 1700;
+    pub const RECORD: u32 = 2249;
+    pub const UUID: u32 = 2950;
+    pub const JSONB: u32 = 3802;
+}
+
+/// How values of a type looked up in `pg_type` are represented on the wire.
+#[derive(Clone, Debug)]
+pub enum TypeKind {
+    Base,
+    Array(u32),
+    Enum,
+    Composite(Vec<(String, u32)>),
+    Domain(u32),
+    Range(u32),
+}
+
+#[derive(Clone, Debug)]
+pub struct TypeDescription {
+    pub name: String,
+    pub kind: TypeKind,
+}
+
+impl TypeDescription {
+    /// Types that must also be known to decode values of this type.
+    pub fn dependencies(&self) -> Vec<u32> {
+        match &self.kind {
+            TypeKind::Base | TypeKind::Enum => Vec::new(),
+            TypeKind::Array(element) => vec![*element],
+            TypeKind::Composite(fields) => fields.iter().map(|(_, field_oid)| *field_oid).collect(),
+            TypeKind::Domain(base) => vec![*base],
+            TypeKind::Range(subtype) => vec![*subtype],
+        }
+    }
+}
+
+pub type TypeCache = HashMap<u32, TypeDescription>;
+
+/// Time zone that TIMESTAMPTZ values are shown in.
+#[derive(Clone, Copy, Debug)]
+pub enum TimeZone {
+    Utc,
+    Local,
+    Named(chrono_tz::Tz),
+}
+
+impl TimeZone {
+    /// Parses a value of the `TimeZone` setting of a session, falling back to UTC for zones unknown to the client.
+    pub fn from_session(name: &str) -> Self {
+        match name.parse::<chrono_tz::Tz>() {
+            Ok(tz) => TimeZone::Named(tz),
+            Err(_) => {
+                log::warn!("Unknown session time zone {}, showing timestamps in UTC", name);
+                TimeZone::Utc
+            },
+        }
+    }
+
+    fn offset_at(&self, datetime: &NaiveDateTime) -> FixedOffset {
+        match self {
+            TimeZone::Utc => Utc.fix(),
+            TimeZone::Local => chrono::Local.from_utc_datetime(datetime).offset().fix(),
+            TimeZone::Named(tz) => tz.from_utc_datetime(datetime).offset().fix(),
+        }
+    }
+}
+
+/// Settings of the session that values are rendered with.
+#[derive(Clone, Copy, Debug)]
+pub struct Formatting {
+    pub time_zone: TimeZone,
+    /// Digits after the decimal point of MONEY values, which `lc_monetary` sets.
+    pub money_digits: u32,
+}
+
+/// Everything besides the value itself that is needed to decode it.
+pub struct Context<'a> {
+    pub types: &'a TypeCache,
+    pub formatting: Formatting,
+}
+
+/// Decodes a single column value of a row.
+///
+/// Values received in text format are already rendered by the server and are returned as is.
+pub fn value(type_info: &PgTypeInfo, raw: PgValueRef<'_>, context: &Context) -> ValueType {
+    if raw.is_null() {
+        return ValueType::Null;
+    }
+
+    let bytes = match raw.as_bytes() {
+        Ok(bytes) => bytes,
+        Err(e) => return ValueType::DecodeError(e.to_string()),
+    };
+
+    if raw.format() == PgValueFormat::Text {
+        return ValueType::Text(String::from_utf8_lossy(bytes).to_string());
+    }
+
+    let Some(type_oid) = type_info.oid() else {
+        return ValueType::Unknown(type_info.to_string());
+    };
+
+    let value = match type_info.kind() {
+        PgTypeKind::Array(_) => array(bytes, context).map(Some),
+        _ => binary(type_oid.0, bytes, context),
+    };
+
+    match value {
+        Ok(Some(value)) => value,
+        Ok(None) => ValueType::Unknown(type_info.to_string()),
+        Err(e) => ValueType::DecodeError(e),
+    }
+}
+
+/// Decodes a value in binary format by its type OID.
+///
+/// Types that are not built in are looked up in the type cache of the context.
+/// Returns `Ok(None)` when the type is not known to the client.
+fn binary(type_oid: u32, bytes: &[u8], context: &Context) -> Result<Option<ValueType>, String> {
+    let value = match type_oid {
+        // Numeric types
+        oid::INT2 => ValueType::Int(read_i16(bytes, 0)? as i32),
+        oid::INT4 => ValueType::Int(read_i32(bytes, 0)?),
+        oid::INT8 => ValueType::BigInt(read_i64(bytes, 0)?),
+        oid::OID => ValueType::BigInt(read_i32(bytes, 0)? as u32 as i64),
+        oid::FLOAT4 => ValueType::Float(f32::from_bits(read_i32(bytes, 0)? as u32) as f64),
+        oid::FLOAT8 => ValueType::Float(f64::from_bits(read_i64(bytes, 0)? as u64)),
+        oid::NUMERIC => ValueType::Numeric(numeric(bytes)?),
+        oid::MONEY => ValueType::Numeric(money(read_i64(bytes, 0)?, context.formatting.money_digits)),
+
+        // Text types
+        oid::CHAR | oid::NAME | oid::TEXT | oid::VARCHAR | oid::XML | oid::UNKNOWN => ValueType::Text(text(bytes)?),
+        oid::BPCHAR => ValueType::Text(text(bytes)?.trim_end().to_string()),
+
+        // Boolean
+        oid::BOOL => ValueType::Bool(*bytes.first().ok_or_else(end_of_data)? != 0),
+
+        // Binary
+        oid::BYTEA => ValueType::Bytea(bytes.to_vec()),
+
+        // Date/Time types
+        oid::TIMESTAMP => ValueType::Text(timestamp(read_i64(bytes, 0)?, None)),
+        oid::TIMESTAMPTZ => ValueType::Text(timestamp(read_i64(bytes, 0)?, Some(context.formatting.time_zone))),
+        oid::DATE => ValueType::Text(date(read_i32(bytes, 0)?)),
+        oid::TIME => ValueType::Text(time(read_i64(bytes, 0)?)),
+        oid::TIMETZ => ValueType::Text(format!(
+            "{}{}",
+            time(read_i64(bytes, 0)?),
+            // The zone is sent in seconds west of UTC
+            offset(-read_i32(bytes, 8)?),
+        )),
+        oid::INTERVAL => ValueType::Interval(interval(bytes)?),
+
+        // Identifier and network types
+        oid::UUID => ValueType::Uuid(uuid(bytes)?),
+        oid::INET | oid::CIDR => ValueType::Inet(inet(bytes)?),
+        oid::MACADDR | oid::MACADDR8 => ValueType::MacAddr(macaddr(bytes)?),
+
+        // JSON types
+        oid::JSON => ValueType::Text(json(bytes)?),
+        oid::JSONB => match bytes.split_first() {
+            Some((1, json_bytes)) => ValueType::Text(json(json_bytes)?),
+            _ => return Err(String::from("Unsupported jsonb version")),
+        },
+
+        // Anonymous records, e.g. `ROW(1, 'a')`
+        oid::RECORD => record(bytes, None, context)?,
+
+        // Unknown/special types that need special handling
+        oid::ACLITEM => ValueType::Text("[ACL permissions]".to_string()),
+
+        // User-defined and other types described in `pg_type`
+        _ => match context.types.get(&type_oid).map(|description| &description.kind) {
+            Some(TypeKind::Array(_)) => array(bytes, context)?,
+            Some(TypeKind::Enum) => ValueType::Text(text(bytes)?),
+            Some(TypeKind::Composite(fields)) => record(bytes, Some(fields), context)?,
+            Some(TypeKind::Domain(base_oid)) => return binary(*base_oid, bytes, context),
+            Some(TypeKind::Range(subtype_oid)) => range(bytes, *subtype_oid, context)?,
+            Some(TypeKind::Base) | None => return Ok(None),
+        },
+    };
+
+    Ok(Some(value))
+}
+
+/// Decodes a value nested in an array, record or range, where unknown types can't fail the whole value.
+fn nested(type_oid: u32, bytes: &[u8], context: &Context) -> Result<ValueType, String> {
+    Ok(binary(type_oid, bytes, context)?.unwrap_or_else(|| {
+        ValueType::Unknown(
+            context
+                .types
+                .get(&type_oid)
+                .map(|description| description.name.clone())
+                .unwrap_or_else(|| format!("oid {}", type_oid)),
+        )
+    }))
+}
+
+fn end_of_data() -> String {
+    String::from("Unexpected end of data")
+}
+
+fn read_i16(bytes: &[u8], pos: usize) -> Result<i16, String> {
+    bytes
+        .get(pos..pos + 2)
+        .map(|b| i16::from_be_bytes([b[0], b[1]]))
+        .ok_or_else(end_of_data)
+}
+
+fn read_i32(bytes: &[u8], pos: usize) -> Result<i32, String> {
+    bytes
+        .get(pos..pos + 4)
+        .map(|b| i32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+        .ok_or_else(end_of_data)
+}
+
+fn read_i64(bytes: &[u8], pos: usize) -> Result<i64, String> {
+    bytes
+        .get(pos..pos + 8)
+        .map(|b| i64::from_be_bytes(b.try_into().unwrap()))
+        .ok_or_else(end_of_data)
+}
+
+fn text(bytes: &[u8]) -> Result<String, String> {
+    String::from_utf8(bytes.to_vec()).map_err(|e| e.to_string())
+}
+
+fn json(bytes: &[u8]) -> Result<String, String> {
+    serde_json::from_slice::<serde_json::Value>(bytes)
+        .map(|v| v.to_string())
+        .map_err(|e| e.to_string())
+}
+
+/// Renders a MONEY value, sent in the smallest units of the currency, as a decimal number without the currency symbol.
+fn money(units: i64, digits: u32) -> String {
+    let scale = 10u64.pow(digits);
+    let sign = if units < 0 { "-" } else { "" };
+    let units = units.unsigned_abs();
+
+    match digits {
+        0 => format!("{}{}", sign, units),
+        _ => format!("{}{}.{:0width$}", sign, units / scale, units % scale, width = digits as usize),
+    }
+}
+
+/// Decodes an array of any dimension into nested `ValueType::Array` values, keeping NULL elements.
+fn array(bytes: &[u8], context: &Context) -> Result<ValueType, String> {
+    let ndim = read_i32(bytes, 0)?;
+    let element_oid = read_i32(bytes, 8)?;
+    if ndim < 0 {
+        return Err(format!("Invalid array dimensions: {}", ndim));
+    }
+
+    let dimensions = (0..ndim as usize)
+        .map(|i| read_i32(bytes, 12 + i * 8).map(|len| len.max(0) as usize))
+        .collect::<Result<Vec<usize>, String>>()?;
+    let lower_bounds = (0..ndim as usize)
+        .map(|i| read_i32(bytes, 16 + i * 8))
+        .collect::<Result<Vec<i32>, String>>()?;
+
+    if dimensions.is_empty() {
+        return Ok(ValueType::Array(Vec::new(), Vec::new()));
+    }
+
+    // Like PostgreSQL, the bounds are shown only when some dimension doesn't start at 1
+    let bounds = match lower_bounds.iter().all(|&lower| lower == 1) {
+        true => Vec::new(),
+        false => lower_bounds
+            .iter()
+            .zip(&dimensions)
+            .map(|(&lower, &len)| (lower, lower.saturating_add(len as i32 - 1)))
+            .collect(),
+    };
+
+    let mut pos = 12 + ndim as usize * 8;
+    let mut elements = Vec::new();
+    for _ in 0..dimensions.iter().product::<usize>() {
+        let len = read_i32(bytes, pos)?;
+        pos += 4;
+
+        if len < 0 {
+            elements.push(ValueType::Null);
+            continue;
+        }
+
+        let element_bytes = bytes.get(pos..pos + len as usize).ok_or_else(end_of_data)?;
+        pos += len as usize;
+
+        elements.push(nested(element_oid as u32, element_bytes, context)?);
+    }
+
+    // Group the flat list of elements from the innermost dimension outwards
+    for &dimension in dimensions.iter().skip(1).rev() {
+        let mut grouped = Vec::with_capacity(elements.len() / dimension.max(1));
+        let mut elements_iter = elements.into_iter();
+        loop {
+            let chunk = elements_iter.by_ref().take(dimension).collect::<Vec<ValueType>>();
+            if chunk.is_empty() {
+                break;
+            }
+            grouped.push(ValueType::Array(chunk, Vec::new()));
+        }
+        elements = grouped;
+    }
+
+    Ok(ValueType::Array(elements, bounds))
+}
+
+/// Decodes a composite value. Anonymous records have no field names, so they are named like in PostgreSQL: `f1`, `f2`, ...
+fn record(bytes: &[u8], fields: Option<&Vec<(String, u32)>>, context: &Context) -> Result<ValueType, String> {
+    let count = read_i32(bytes, 0)?;
+
+    let mut pos = 4;
+    let mut values = Vec::new();
+    for index in 0..count.max(0) as usize {
+        let field_oid = read_i32(bytes, pos)? as u32;
+        let len = read_i32(bytes, pos + 4)?;
+        pos += 8;
+
+        let name = fields
+            .and_then(|fields| fields.get(index))
+            .map(|(name, _)| name.clone())
+            .unwrap_or_else(|| format!("f{}", index + 1));
+
+        if len < 0 {
+            values.push((name, ValueType::Null));
+            continue;
+        }
+
+        let field_bytes = bytes.get(pos..pos + len as usize).ok_or_else(end_of_data)?;
+        pos += len as usize;
+
+        values.push((name, nested(field_oid, field_bytes, context)?));
+    }
+
+    Ok(ValueType::Record(values))
+}
+
+fn range(bytes: &[u8], subtype_oid: u32, context: &Context) -> Result<ValueType, String> {
+    let flags = *bytes.first().ok_or_else(end_of_data)?;
+    if flags & RANGE_EMPTY != 0 {
+        return Ok(ValueType::Range(None));
+    }
+
+    let mut pos = 1;
+    let mut read_bound = |infinite: bool| -> Result<Option<ValueType>, String> {
+        if infinite {
+            return Ok(None);
+        }
+
+        let len = read_i32(bytes, pos)?;
+        let bound_bytes = bytes.get(pos + 4..pos + 4 + len.max(0) as usize).ok_or_else(end_of_data)?;
+        pos += 4 + len.max(0) as usize;
+
+        nested(subtype_oid, bound_bytes, context).map(Some)
+    };
+
+    let lower = read_bound(flags & RANGE_LB_INF != 0)?;
+    let upper = read_bound(flags & RANGE_UB_INF != 0)?;
+
+    Ok(ValueType::Range(Some(Box::new(RangeValue {
+        lower,
+        upper,
+        lower_inclusive: flags & RANGE_LB_INC != 0,
+        upper_inclusive: flags & RANGE_UB_INC != 0,
+    }))))
+}
+
+fn postgres_epoch() -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(2000, 1, 1).unwrap().and_time(NaiveTime::MIN)
+}
+
+/// Renders a date like PostgreSQL's ISO DateStyle, with ` BC` suffix for years before 1 AD.
+/// The year is astronomical, `0` is 1 BC.
+fn iso_date((year, month, day): (i64, u32, u32)) -> (String, &'static str) {
+    if year > 0 {
+        (format!("{:04}-{:02}-{:02}", year, month, day), "")
+    } else {
+        (format!("{:04}-{:02}-{:02}", 1 - year, month, day), " BC")
+    }
+}
+
+/// Year, month and day of the proleptic Gregorian calendar for days since 2000-01-01.
+/// Computed without chrono, whose dates end long before the ones PostgreSQL stores.
+fn civil_date(days: i64) -> (i64, u32, u32) {
+    // Days since 0000-03-01, so that leap days end the year
+    let days = days + 730425;
+    let era = days.div_euclid(146097);
+    let day_of_era = days.rem_euclid(146097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_from_march = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_from_march + 2) / 5 + 1) as u32;
+    let month = if month_from_march < 10 { month_from_march + 3 } else { month_from_march - 9 } as u32;
+    let year = era * 400 + year_of_era + if month <= 2 { 1 } else { 0 };
+
+    (year, month, day)
+}
+
+/// Renders an UTC offset like PostgreSQL: `+03`, `+05:30`, `-00:25:21`.
+fn offset(seconds: i32) -> String {
+    let sign = if seconds < 0 { '-' } else { '+' };
+    let seconds = seconds.abs();
+
+    let mut result = format!("{}{:02}", sign, seconds / 3600);
+    if seconds % 3600 != 0 {
+        result.push_str(&format!(":{:02}", seconds / 60 % 60));
+    }
+    if seconds % 60 != 0 {
+        result.push_str(&format!(":{:02}", seconds % 60));
+    }
+
+    result
+}
+
+/// Renders a TIMESTAMP, or a TIMESTAMPTZ when a time zone to show it in is given.
+fn timestamp(micros: i64, time_zone: Option<TimeZone>) -> String {
+    const MICROS_PER_DAY: i64 = 86_400_000_000;
+
+    match micros {
+        i64::MAX => return String::from("infinity"),
+        i64::MIN => return String::from("-infinity"),
+        _ => {},
+    }
+
+    let (local, offset) = match time_zone {
+        Some(time_zone) => {
+            // Past the range of chrono the offset at its last representable moment applies
+            let utc = postgres_epoch()
+                .checked_add_signed(TimeDelta::microseconds(micros))
+                .unwrap_or(if micros < 0 { NaiveDateTime::MIN } else { NaiveDateTime::MAX });
+            let offset_seconds = time_zone.offset_at(&utc).local_minus_utc();
+
+            (micros as i128 + offset_seconds as i128 * 1_000_000, offset(offset_seconds))
+        },
+        None => (micros as i128, String::new()),
+    };
+
+    let days = local.div_euclid(MICROS_PER_DAY as i128) as i64;
+    let (date, era) = iso_date(civil_date(days));
+    let time = time(local.rem_euclid(MICROS_PER_DAY as i128) as i64);
+
+    format!("{} {}{}{}", date, time, offset, era)
+}
+
+fn date(days: i32) -> String {
+    match days {
+        i32::MAX => String::from("infinity"),
+        i32::MIN => String::from("-infinity"),
+        _ => {
+            let (date, era) = iso_date(civil_date(days as i64));
+            format!("{}{}", date, era)
+        },
+    }
+}
+
+/// Renders a time of day given in microseconds since midnight, trailing zeros of the fraction are dropped.
+fn time(micros: i64) -> String {
+    let mut result = format!(
+        "{:02}:{:02}:{:02}",
+        micros / 3_600_000_000,
+        micros / 60_000_000 % 60,
+        micros / 1_000_000 % 60,
+    );
+
+    let fraction = micros % 1_000_000;
+    if fraction != 0 {
+        result.push('.');
+        result.push_str(format!("{:06}", fraction).trim_end_matches('0'));
+    }
+
+    result
+}
+
+/// Renders a binary NUMERIC value exactly like PostgreSQL does, keeping the declared scale.
+pub fn numeric(bytes: &[u8]) -> Result<String, String> {
+    let ndigits = read_i16(bytes, 0)?;
+    let weight = read_i16(bytes, 2)?;
+    let sign = read_i16(bytes, 4)? as u16;
+    let dscale = read_i16(bytes, 6)?;
+
+    match sign {
+        NUMERIC_NAN => return Ok(String::from("NaN")),
+        NUMERIC_PINF => return Ok(String::from("Infinity")),
+        NUMERIC_NINF => return Ok(String::from("-Infinity")),
+        NUMERIC_POS | NUMERIC_NEG => {},
+        _ => return Err(format!("Invalid numeric sign: {:#06x}", sign)),
+    }
+
+    if ndigits < 0 || dscale < 0 {
+        return Err(String::from("Invalid numeric header"));
+    }
+
+    let digits = (0..ndigits as usize)
+        .map(|i| read_i16(bytes, 8 + i * 2))
+        .collect::<Result<Vec<i16>, String>>()?;
+    let digit_at = |index: i32| -> i16 {
+        if index < 0 {
+            0
+        } else {
+            digits.get(index as usize).copied().unwrap_or(0)
+        }
+    };
+
+    let mut result = String::new();
+    if sign == NUMERIC_NEG {
+        result.push('-');
+    }
+
+    if weight < 0 {
+        result.push('0');
+    } else {
+        for index in 0..=weight as i32 {
+            if index == 0 {
+                result.push_str(&digit_at(index).to_string());
+            } else {
+                result.push_str(&format!("{:04}", digit_at(index)));
+            }
+        }
+    }
+
+    if dscale > 0 {
+        result.push('.');
+
+        let mut fraction = String::new();
+        let mut index = weight as i32 + 1;
+        while fraction.len() < dscale as usize {
+            fraction.push_str(&format!("{:04}", digit_at(index)));
+            index += 1;
+        }
+        fraction.truncate(dscale as usize);
+
+        result.push_str(&fraction);
+    }
+
+    Ok(result)
+}
+
+pub fn uuid(bytes: &[u8]) -> Result<String, String> {
+    if bytes.len() != 16 {
+        return Err(format!("Invalid uuid length: {}", bytes.len()));
+    }
+
+    let hex = bytes.iter().map(|b| format!("{:02x}", b)).collect::<String>();
+
+    Ok(format!("{}-{}-{}-{}-{}", &hex[0..8], &hex[8..12], &hex[12..16], &hex[16..20], &hex[20..32]))
+}
+
+/// Renders INET and CIDR values. Like PostgreSQL, host addresses of INET omit the prefix length.
+pub fn inet(bytes: &[u8]) -> Result<String, String> {
+    if bytes.len() < 4 {
+        return Err(end_of_data());
+    }
+
+    let family = bytes[0];
+    let bits = bytes[1];
+    let is_cidr = bytes[2] != 0;
+    let address = &bytes[4..];
+
+    let (address, max_bits) = match family {
+        PGSQL_AF_INET => {
+            let octets: [u8; 4] = address.try_into().map_err(|_| String::from("Invalid inet address length"))?;
+            (std::net::Ipv4Addr::from(octets).to_string(), 32)
+        },
+        PGSQL_AF_INET6 => {
+            let octets: [u8; 16] = address.try_into().map_err(|_| String::from("Invalid inet address length"))?;
+            (std::net::Ipv6Addr::from(octets).to_string(), 128)
+        },
+        _ => return Err(format!("Invalid inet family: {}", family)),
+    };
+
+    if !is_cidr && bits == max_bits {
+        Ok(address)
+    } else {
+        Ok(format!("{}/{}", address, bits))
+    }
+}
+
+/// Renders MACADDR (6 bytes) and MACADDR8 (8 bytes) values.
+pub fn macaddr(bytes: &[u8]) -> Result<String, String> {
+    if bytes.len() != 6 && bytes.len() != 8 {
+        return Err(format!("Invalid macaddr length: {}", bytes.len()));
+    }
+
+    Ok(bytes.iter().map(|b| format!("{:02x}", b)).collect::<Vec<String>>().join(":"))
+}
+
+/// Renders an interval in PostgreSQL's default `postgres` IntervalStyle, e.g. `1 year 2 mons -3 days +04:05:06.5`.
+pub fn interval(bytes: &[u8]) -> Result<String, String> {
+    let micros = read_i64(bytes, 0)?;
+    let days = read_i32(bytes, 8)?;
+    let months = read_i32(bytes, 12)?;
+
+    let mut result = String::new();
+    let mut is_zero = true;
+    let mut is_before = false;
+
+    let add_part = |result: &mut String, value: i64, units: &str, is_zero: &mut bool, is_before: &mut bool| {
+        if value == 0 {
+            return;
+        }
+
+        result.push_str(&format!(
+            "{}{}{} {}{}",
+            if *is_zero { "" } else { " " },
+            if *is_before && value > 0 { "+" } else { "" },
+            value,
+            units,
+            if value != 1 { "s" } else { "" },
+        ));
+
+        *is_before = value < 0;
+        *is_zero = false;
+    };
+
+    add_part(&mut result, (months / 12) as i64, "year", &mut is_zero, &mut is_before);
+    add_part(&mut result, (months % 12) as i64, "mon", &mut is_zero, &mut is_before);
+    add_part(&mut result, days as i64, "day", &mut is_zero, &mut is_before);
+
+    let hours = micros / 3_600_000_000;
+    let minutes = micros / 60_000_000 % 60;
+    let seconds = micros / 1_000_000 % 60;
+    let fraction = micros % 1_000_000;
+
+    if is_zero || micros != 0 {
+        let minus = micros < 0;
+
+        result.push_str(&format!(
+            "{}{}{:02}:{:02}:{:02}",
+            if is_zero { "" } else { " " },
+            if minus { "-" } else if is_before { "+" } else { "" },
+            hours.abs(),
+            minutes.abs(),
+            seconds.abs(),
+        ));
+
+        if fraction != 0 {
+            let fraction = format!("{:06}", fraction.abs());
+            result.push('.');
+            result.push_str(fraction.trim_end_matches('0'));
+        }
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn date_bounds() {
+        assert_eq!(date(0), "2000-01-01");
+        assert_eq!(date(59), "2000-02-29");
+        assert_eq!(date(-2451179), "4713-11-24 BC");
+        assert_eq!(date(2145031948), "5874897-12-31");
+        assert_eq!(date(i32::MAX), "infinity");
+        assert_eq!(date(i32::MIN), "-infinity");
+    }
+
+    #[test]
+    fn timestamp_bounds() {
+        assert_eq!(timestamp(-1, None), "1999-12-31 23:59:59.999999");
+        assert_eq!(timestamp(-211781865600000000, None), "4713-11-24 00:00:00 BC");
+        assert_eq!(timestamp(9223371331199999999, None), "294276-12-31 23:59:59.999999");
+        assert_eq!(timestamp(9223371331199999999, Some(TimeZone::Utc)), "294276-12-31 23:59:59.999999+00");
+        assert_eq!(
+            timestamp(-211781865600000000, Some(TimeZone::Named(chrono_tz::Europe::Moscow))),
+            "4713-11-24 02:30:17+02:30:17 BC",
+        );
+    }
+
+    #[test]
+    fn money_digits() {
+        assert_eq!(money(1234, 2), "12.34");
+        assert_eq!(money(-5, 2), "-0.05");
+        assert_eq!(money(1234, 0), "1234");
+        assert_eq!(money(i64::MIN, 2), "-92233720368547758.08");
+    }
+
+    #[test]
+    fn array_bounds() {
+        let types = TypeCache::new();
+        let context = Context { types: &types, formatting: Formatting { time_zone: TimeZone::Utc, money_digits: 2 } };
+
+        // Binary INT4 array of 3 elements with the lower bound given
+        let int4_array = |lower: i32| {
+            let mut bytes = Vec::new();
+            for header in [1, 0, oid::INT4 as i32, 3, lower] {
+                bytes.extend(header.to_be_bytes());
+            }
+            for element in 1..=3i32 {
+                bytes.extend(4i32.to_be_bytes());
+                bytes.extend(element.to_be_bytes());
+            }
+            bytes
+        };
+
+        assert_eq!(array(&int4_array(1), &context).unwrap().to_string(), "{1,2,3}");
+        assert_eq!(array(&int4_array(0), &context).unwrap().to_string(), "[0:2]={1,2,3}");
+        assert_eq!(array(&int4_array(-5), &context).unwrap().to_string(), "[-5:-3]={1,2,3}");
+    }
+
+    #[test]
+    fn bytea_is_hex() {
+        let types = TypeCache::new();
+        let context = Context { types: &types, formatting: Formatting { time_zone: TimeZone::Utc, money_digits: 2 } };
+
+        // Valid UTF-8 stays binary too
+        let bytea = |bytes: &[u8]| binary(oid::BYTEA, bytes, &context).unwrap().unwrap().to_string();
+        assert_eq!(bytea(b"abc"), "\\x616263");
+        assert_eq!(bytea(&[0x00, 0xff]), "\\x00ff");
+        assert_eq!(bytea(&[]), "\\x");
+    }
+}
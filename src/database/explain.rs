@@ -0,0 +1,279 @@
+use serde_json::{Map, Value};
+
+use crate::data::structs::{ResultSet, ValueType};
+
+use super::decode::oid;
+
+/// How a statement is explained.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ExplainMode {
+    /// Shows the plan the server chose without running the statement.
+    Plan,
+    /// Runs the statement and shows the plan with timings, row counts and buffers.
+    Analyze,
+}
+
+impl ExplainMode {
+    /// Wraps the statement into `EXPLAIN` returning the plan as JSON.
+    pub fn wrap(&self, statement: &str) -> String {
+        match self {
+            ExplainMode::Plan => format!("EXPLAIN (FORMAT JSON) {}", statement),
+            ExplainMode::Analyze => format!("EXPLAIN (ANALYZE, BUFFERS, FORMAT JSON) {}", statement),
+        }
+    }
+}
+
+/// Plan of a statement as returned by `EXPLAIN (FORMAT JSON)`.
+#[derive(Clone, Debug)]
+pub struct QueryPlan {
+    pub root: PlanNode,
+    pub planning_time: Option<f64>,
+    pub execution_time: Option<f64>,
+    /// Time spent in triggers by name, in milliseconds.
+    pub triggers: Vec<(String, f64)>,
+}
+
+/// Node of a plan, times are in milliseconds.
+#[derive(Clone, Debug)]
+pub struct PlanNode {
+    pub node_type: String,
+    /// Relation, index, function or CTE the node reads.
+    pub relation: Option<String>,
+    pub startup_cost: f64,
+    pub total_cost: f64,
+    pub plan_rows: f64,
+    /// Measured by `EXPLAIN ANALYZE`, `None` without it.
+    pub actual: Option<ActualStatistics>,
+    /// Block counts, `None` unless `BUFFERS` was asked for.
+    pub buffers: Option<Buffers>,
+    /// Cost of the node without its children.
+    pub self_cost: f64,
+    /// Time spent in the node without its children over all loops.
+    pub self_time: Option<f64>,
+    /// Conditions, keys and other properties the node has.
+    pub details: Vec<(String, String)>,
+    pub children: Vec<PlanNode>,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ActualStatistics {
+    pub startup_time: f64,
+    /// Time per loop.
+    pub total_time: f64,
+    /// Rows per loop.
+    pub rows: f64,
+    /// `0` if the node never ran.
+    pub loops: f64,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Buffers {
+    pub shared_hit: u64,
+    pub shared_read: u64,
+    pub shared_dirtied: u64,
+    pub shared_written: u64,
+    pub temp_read: u64,
+    pub temp_written: u64,
+}
+
+/// Keys shown in the node title and statistics rather than in its details.
+const SUMMARY_KEYS: &[&str] = &[
+    "Node Type", "Join Type", "Strategy", "Parallel Aware", "Async Capable",
+    "Relation Name", "Schema", "Alias", "Index Name", "CTE Name", "Function Name",
+    "Startup Cost", "Total Cost", "Plan Rows", "Plan Width",
+    "Actual Startup Time", "Actual Total Time", "Actual Rows", "Actual Loops",
+    "Shared Hit Blocks", "Shared Read Blocks", "Shared Dirtied Blocks", "Shared Written Blocks",
+    "Local Hit Blocks", "Local Read Blocks", "Local Dirtied Blocks", "Local Written Blocks",
+    "Temp Read Blocks", "Temp Written Blocks", "Plans",
+];
+
+impl QueryPlan {
+    /// Finds the plan in the result of `EXPLAIN (FORMAT JSON)`, `None` for any other result.
+    pub fn from_result(result: &ResultSet) -> Option<Self> {
+        let [column] = result.columns.as_slice() else {
+            return None;
+        };
+        if column.name != "QUERY PLAN" || column.type_oid != oid::JSON {
+            return None;
+        }
+
+        match result.rows.as_slice() {
+            [row] => match row.as_slice() {
+                [ValueType::Text(json)] => Self::parse(json).ok(),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    pub fn parse(json: &str) -> Result<Self, String> {
+        let value: Value = serde_json::from_str(json).map_err(|e| e.to_string())?;
+        let explain = value
+            .as_array()
+            .and_then(|statements| statements.first())
+            .and_then(Value::as_object)
+            .ok_or_else(|| String::from("Plan is not an array of objects"))?;
+        let plan = explain
+            .get("Plan")
+            .and_then(Value::as_object)
+            .ok_or_else(|| String::from("Plan has no root node"))?;
+
+        Ok(Self {
+            root: PlanNode::parse(plan),
+            planning_time: explain.get("Planning Time").and_then(Value::as_f64),
+            execution_time: explain.get("Execution Time").and_then(Value::as_f64),
+            triggers: explain
+                .get("Triggers")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(|trigger| Some((
+                    trigger.get("Trigger Name")?.as_str()?.to_string(),
+                    trigger.get("Time")?.as_f64()?,
+                )))
+                .collect(),
+        })
+    }
+
+    /// Share of the statement the node takes by itself, from `0` to `1`.
+    /// Measured by time when the plan was analyzed and estimated by cost otherwise.
+    pub fn share(&self, node: &PlanNode) -> f64 {
+        let (part, total) = match (node.self_time, self.root.actual) {
+            (Some(self_time), Some(actual)) => (self_time, actual.total_time * actual.loops),
+            _ => (node.self_cost, self.root.total_cost),
+        };
+
+        if total > 0.0 {
+            (part / total).clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+}
+
+impl PlanNode {
+    fn parse(node: &Map<String, Value>) -> Self {
+        let number = |key: &str| node.get(key).and_then(Value::as_f64);
+        let text = |key: &str| node.get(key).and_then(Value::as_str);
+
+        let children: Vec<PlanNode> = node
+            .get("Plans")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_object)
+            .map(Self::parse)
+            .collect();
+
+        let actual = match (number("Actual Total Time"), number("Actual Loops")) {
+            (Some(total_time), Some(loops)) => Some(ActualStatistics {
+                startup_time: number("Actual Startup Time").unwrap_or_default(),
+                total_time,
+                rows: number("Actual Rows").unwrap_or_default(),
+                loops,
+            }),
+            _ => None,
+        };
+
+        let blocks = |key: &str| node.get(key).and_then(Value::as_u64);
+        let buffers = blocks("Shared Hit Blocks").map(|shared_hit| Buffers {
+            shared_hit,
+            shared_read: blocks("Shared Read Blocks").unwrap_or_default(),
+            shared_dirtied: blocks("Shared Dirtied Blocks").unwrap_or_default(),
+            shared_written: blocks("Shared Written Blocks").unwrap_or_default(),
+            temp_read: blocks("Temp Read Blocks").unwrap_or_default(),
+            temp_written: blocks("Temp Written Blocks").unwrap_or_default(),
+        });
+
+        let total_cost = number("Total Cost").unwrap_or_default();
+        // The inner side of a nested loop is costed per loop and rescanned for every outer row
+        let children_cost: f64 = match (text("Node Type"), children.as_slice()) {
+            (Some("Nested Loop"), [outer, inner]) => outer.total_cost + inner.total_cost * outer.plan_rows,
+            _ => children.iter().map(|child| child.total_cost).sum(),
+        };
+
+        let self_time = actual.map(|actual| {
+            let children_time: f64 = children
+                .iter()
+                .filter_map(|child| child.actual)
+                .map(|child| child.total_time * child.loops)
+                .sum();
+
+            (actual.total_time * actual.loops - children_time).max(0.0)
+        });
+
+        let mut node_type = text("Node Type").unwrap_or("Unknown").to_string();
+        if node.get("Parallel Aware").and_then(Value::as_bool) == Some(true) {
+            node_type = format!("Parallel {}", node_type);
+        }
+        match (text("Join Type"), text("Strategy")) {
+            (Some(join_type), _) if join_type != "Inner" => {
+                node_type = node_type.replacen(" Join", &format!(" {} Join", join_type), 1);
+                if node_type == "Nested Loop" {
+                    node_type = format!("Nested Loop {} Join", join_type);
+                }
+            },
+            (_, Some("Hashed")) if node_type == "Aggregate" => node_type = String::from("HashAggregate"),
+            (_, Some("Sorted")) if node_type == "Aggregate" => node_type = String::from("GroupAggregate"),
+            (_, Some("Mixed")) if node_type == "Aggregate" => node_type = String::from("MixedAggregate"),
+            _ => {},
+        }
+
+        // Like the text format, the alias is only shown when it differs from the name
+        let name = text("Relation Name").or(text("CTE Name")).or(text("Function Name"));
+        let relation = name.map(|name| {
+            let mut relation = match (text("Schema"), text("Relation Name")) {
+                (Some(schema), Some(_)) => format!("{}.{}", schema, name),
+                _ => name.to_string(),
+            };
+            if text("Function Name").is_some() {
+                relation.push_str("()");
+            }
+            match text("Alias") {
+                Some(alias) if alias != name => format!("{} {}", relation, alias),
+                _ => relation,
+            }
+        });
+        let relation = match (text("Index Name"), relation) {
+            (Some(index), Some(relation)) => Some(format!("using {} on {}", index, relation)),
+            (Some(index), None) => Some(format!("on {}", index)),
+            (None, relation) => relation.map(|relation| format!("on {}", relation)),
+        };
+
+        let details = node
+            .iter()
+            .filter(|(key, _)| !SUMMARY_KEYS.contains(&key.as_str()))
+            .map(|(key, value)| (key.clone(), detail_text(value)))
+            .collect();
+
+        Self {
+            node_type,
+            relation,
+            startup_cost: number("Startup Cost").unwrap_or_default(),
+            total_cost,
+            plan_rows: number("Plan Rows").unwrap_or_default(),
+            actual,
+            buffers,
+            self_cost: (total_cost - children_cost).max(0.0),
+            self_time,
+            details,
+            children,
+        }
+    }
+
+    /// How many times the estimated row count is off from the actual one, over `1` either way.
+    pub fn rows_misestimate(&self) -> Option<f64> {
+        let actual = self.actual.filter(|actual| actual.loops > 0.0)?;
+
+        Some(actual.rows.max(1.0) / self.plan_rows.max(1.0))
+            .map(|ratio| if ratio < 1.0 { 1.0 / ratio } else { ratio })
+    }
+}
+
+fn detail_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Array(values) => values.iter().map(detail_text).collect::<Vec<String>>().join(", "),
+        value => value.to_string(),
+    }
+}
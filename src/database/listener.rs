@@ -0,0 +1,190 @@
+use chrono::{DateTime, Local};
+use sqlx::postgres::PgListener;
+use sqlx_postgres::PgPoolOptions;
+use tokio::sync::mpsc;
+
+use std::sync::{Arc, Mutex};
+
+use super::Database;
+
+/// Notifications kept by a listener, the oldest ones are dropped first.
+const MAX_NOTIFICATIONS: usize = 10000;
+
+/// Notification received on a listened channel.
+#[derive(Clone, Debug)]
+pub struct Notification {
+    pub time: DateTime<Local>,
+    pub channel: String,
+    pub payload: String,
+    /// Payload pretty-printed if it's a JSON object or array.
+    pub json_payload: Option<String>,
+    /// PID of the backend that sent the notification.
+    pub process_id: u32,
+}
+
+enum Command {
+    Listen(String),
+    Unlisten(String),
+    Notify(String, String),
+}
+
+/// Dedicated connection of a listener page, receiving notifications in the background.
+/// Dropping the last handle closes the connection.
+#[derive(Clone)]
+pub struct Listener {
+    commands: mpsc::UnboundedSender<Command>,
+    state: Arc<Mutex<ListenerState>>,
+}
+
+#[derive(Default)]
+struct ListenerState {
+    connected: bool,
+    channels: Vec<String>,
+    notifications: Vec<Notification>,
+    error: Option<String>,
+}
+
+impl Listener {
+    /// Whether the connection is open and waits for notifications.
+    pub fn is_connected(&self) -> bool {
+        self.state.lock().unwrap().connected
+    }
+
+    /// Channels the connection listens on.
+    pub fn channels(&self) -> Vec<String> {
+        self.state.lock().unwrap().channels.clone()
+    }
+
+    /// Last error of the connection or of a command sent to it.
+    pub fn error(&self) -> Option<String> {
+        self.state.lock().unwrap().error.clone()
+    }
+
+    /// Runs `f` over the notifications received so far, oldest first.
+    pub fn with_notifications<R>(&self, f: impl FnOnce(&[Notification]) -> R) -> R {
+        f(&self.state.lock().unwrap().notifications)
+    }
+
+    pub fn clear_notifications(&self) {
+        self.state.lock().unwrap().notifications.clear();
+    }
+
+    pub fn listen(&self, channel: impl ToString) {
+        let _ = self.commands.send(Command::Listen(channel.to_string()));
+    }
+
+    pub fn unlisten(&self, channel: impl ToString) {
+        let _ = self.commands.send(Command::Unlisten(channel.to_string()));
+    }
+
+    /// Sends a notification to the channel through the listener connection.
+    pub fn notify(&self, channel: impl ToString, payload: impl ToString) {
+        let _ = self.commands.send(Command::Notify(channel.to_string(), payload.to_string()));
+    }
+
+    async fn run(database: Database, channels: Vec<String>, mut commands: mpsc::UnboundedReceiver<Command>, state: Arc<Mutex<ListenerState>>) {
+        let mut listener = match Self::connect(&database, &channels).await {
+            Ok(listener) => listener,
+            Err(e) => {
+                state.lock().unwrap().error = Some(e);
+                return;
+            },
+        };
+
+        {
+            let mut state = state.lock().unwrap();
+            state.connected = true;
+            state.channels = channels;
+        }
+
+        loop {
+            // Receiving is cancel-safe, a notification read in part is finished on the next call
+            tokio::select! {
+                command = commands.recv() => {
+                    let result = match command {
+                        Some(Command::Listen(channel)) => listener.listen(&channel).await.map(|_| {
+                            let mut state = state.lock().unwrap();
+                            if !state.channels.contains(&channel) {
+                                state.channels.push(channel);
+                            }
+                        }),
+                        Some(Command::Unlisten(channel)) => listener.unlisten(&channel).await.map(|_| {
+                            state.lock().unwrap().channels.retain(|listened| *listened != channel);
+                        }),
+                        Some(Command::Notify(channel, payload)) => sqlx::query("SELECT pg_notify($1, $2)")
+                            .bind(channel)
+                            .bind(payload)
+                            .execute(&mut listener)
+                            .await
+                            .map(|_| ()),
+                        // The page was closed
+                        None => break,
+                    };
+
+                    state.lock().unwrap().error = result.err().map(|e| e.to_string());
+                },
+                notification = listener.recv() => {
+                    let mut state = state.lock().unwrap();
+
+                    match notification {
+                        Ok(notification) => {
+                            let json_payload = serde_json::from_str::<serde_json::Value>(notification.payload())
+                                .ok()
+                                .filter(|value| value.is_object() || value.is_array())
+                                .and_then(|value| serde_json::to_string_pretty(&value).ok());
+
+                            if state.notifications.len() >= MAX_NOTIFICATIONS {
+                                state.notifications.remove(0);
+                            }
+                            state.notifications.push(Notification {
+                                time: Local::now(),
+                                channel: notification.channel().to_string(),
+                                payload: notification.payload().to_string(),
+                                json_payload,
+                                process_id: notification.process_id(),
+                            });
+                        },
+                        // Lost connections are reopened while receiving, so this one can't be
+                        Err(e) => {
+                            state.connected = false;
+                            state.error = Some(e.to_string());
+                            break;
+                        },
+                    }
+                },
+            }
+        }
+    }
+
+    /// Opens the listener on a pool of its own, which reopens the connection if it's lost.
+    async fn connect(database: &Database, channels: &[String]) -> Result<PgListener, String> {
+        let pool = PgPoolOptions::new()
+            .max_connections(1)
+            .max_lifetime(None)
+            .idle_timeout(None)
+            .connect_with(database.pool.connect_options().as_ref().clone())
+            .await
+            .map_err(|e| e.to_string())?;
+
+        let mut listener = PgListener::connect_with(&pool).await.map_err(|e| e.to_string())?;
+        listener.ignore_pool_close_event(true);
+        listener
+            .listen_all(channels.iter().map(String::as_str))
+            .await
+            .map_err(|e| e.to_string())?;
+
+        Ok(listener)
+    }
+}
+
+impl Database {
+    /// Opens a connection listening on the channels in the background of the runtime.
+    pub fn listener(&self, runtime: &tokio::runtime::Runtime, channels: Vec<String>) -> Listener {
+        let (commands, receiver) = mpsc::unbounded_channel();
+        let state = Arc::new(Mutex::new(ListenerState::default()));
+
+        runtime.spawn(Listener::run(self.clone(), channels, receiver, state.clone()));
+
+        Listener { commands, state }
+    }
+}
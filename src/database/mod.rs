@@ -0,0 +1,934 @@
+pub mod copy;
+mod decode;
+pub mod explain;
+pub mod listener;
+pub mod script;
+mod session;
+
+pub use decode::oid;
+pub use session::{Session, TransactionStatus, CURSOR_TRANSACTION_TIMEOUT};
+
+use sqlx::{Arguments, Column, Either, Executor, PgPool, Row, TypeInfo};
+use sqlx::postgres::types::Oid;
+use sqlx_postgres::{PgArguments, PgColumn, PgConnectOptions, PgConnection, PgDatabaseError, PgErrorPosition, PgPoolOptions, PgQueryResult, PgRow};
+
+use futures_util::stream::BoxStream;
+use futures_util::TryStreamExt;
+
+use crate::data::ROWS_PER_PAGE;
+use crate::data::structs::{ColumnDescription, DisplayTimeZone, Table, ValueType};
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+
+const CURSOR_NAME: &str = "rs_postgres_cursor";
+
+#[derive(Clone)]
+pub struct Database {
+    pool: PgPool,
+    types: Arc<Mutex<decode::TypeCache>>,
+    timeouts: QueryTimeouts,
+}
+
+/// How queries from SQL pages are run and their rows displayed.
+#[derive(Clone, Copy, Debug)]
+pub struct QueryOptions {
+    pub display_time_zone: DisplayTimeZone,
+    /// Rows read before the query is paused.
+    pub max_rows: u32,
+    /// Overrides the timeouts of the server.
+    pub timeouts: Option<QueryTimeouts>,
+}
+
+/// Values of statement placeholders, `None` binds NULL.
+pub type ParameterValues = HashMap<script::Parameter, Option<String>>;
+
+/// Limits in seconds for queries run from SQL pages, 0 disables a limit.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct QueryTimeouts {
+    pub statement_timeout: u32,
+    pub lock_timeout: u32,
+}
+
+/// TLS negotiated by a connection, as reported by `pg_stat_ssl`.
+#[derive(Clone, Debug)]
+pub struct TlsState {
+    pub version: String,
+    pub cipher: String,
+}
+
+#[derive(Clone, Debug)]
+pub enum QueryError {
+    /// The query was cancelled by `statement_timeout` after the given number of seconds.
+    StatementTimeout(u32),
+    /// The query was cancelled by `lock_timeout` after the given number of seconds.
+    LockTimeout(u32),
+    /// The server rejected the query.
+    Database(Box<DatabaseError>),
+    Other(String),
+}
+
+/// Fields of an error reported by the server, see "Error and Notice Message Fields" in the PostgreSQL docs.
+#[derive(Clone, Debug, Default)]
+pub struct DatabaseError {
+    pub severity: String,
+    /// SQLSTATE code.
+    pub code: String,
+    pub message: String,
+    pub detail: Option<String>,
+    pub hint: Option<String>,
+    /// Byte offset of the failing character in the query as it was given, once it's located.
+    pub position: Option<usize>,
+    /// Query run by a function that failed, with the byte offset of the failing character in it.
+    pub internal_query: Option<(String, usize)>,
+    /// Call stack of the functions the error happened in.
+    pub r#where: Option<String>,
+    pub schema: Option<String>,
+    pub table: Option<String>,
+    pub column: Option<String>,
+    pub data_type: Option<String>,
+    pub constraint: Option<String>,
+    /// Character position in the text sent to the server as reported by it, counted from 1.
+    server_position: Option<usize>,
+}
+
+impl QueryError {
+    /// The server cancelled the query, on request or by `statement_timeout`, which it reports alike.
+    pub fn is_query_canceled(&self) -> bool {
+        match self {
+            QueryError::StatementTimeout(_) => true,
+            QueryError::Database(error) => error.code == "57014",
+            _ => false,
+        }
+    }
+
+    fn from_sqlx(error: sqlx::Error, timeouts: QueryTimeouts) -> Self {
+        let code = error.as_database_error().and_then(|e| e.code()).unwrap_or_default();
+        let message = error.as_database_error().map(|e| e.message()).unwrap_or_default();
+
+        // Cancel requests and NOWAIT locks fail with the same codes, only the message tells the timeouts apart
+        match (code.as_ref(), message) {
+            ("57014", "canceling statement due to statement timeout") if timeouts.statement_timeout > 0 => QueryError::StatementTimeout(timeouts.statement_timeout),
+            ("55P03", "canceling statement due to lock timeout") if timeouts.lock_timeout > 0 => QueryError::LockTimeout(timeouts.lock_timeout),
+            _ => match error.as_database_error().and_then(|e| e.try_downcast_ref::<PgDatabaseError>()) {
+                Some(e) => QueryError::Database(Box::new(DatabaseError::from_pg(e))),
+                None => QueryError::Other(error.to_string()),
+            },
+        }
+    }
+
+    /// Finds the failing character of the statement the error is about,
+    /// `sql_ascii` tells the server counted the position in bytes.
+    fn locate(mut self, statement: &script::ReplacedStatement, sql_ascii: bool) -> Self {
+        if let QueryError::Database(error) = &mut self {
+            error.position = error.server_position
+                .map(|position| match sql_ascii {
+                    true => byte_offset(&statement.text, position),
+                    false => char_offset(&statement.text, position),
+                })
+                .map(|offset| statement.original_offset(offset));
+        }
+
+        self
+    }
+}
+
+impl DatabaseError {
+    fn from_pg(error: &PgDatabaseError) -> Self {
+        let (server_position, internal_query) = match error.position() {
+            Some(PgErrorPosition::Original(position)) => (Some(position), None),
+            Some(PgErrorPosition::Internal { position, query }) => (None, Some((query.to_string(), char_offset(query, position)))),
+            None => (None, None),
+        };
+
+        Self {
+            severity: format!("{:?}", error.severity()).to_uppercase(),
+            code: error.code().to_string(),
+            message: error.message().to_string(),
+            detail: error.detail().map(str::to_string),
+            hint: error.hint().map(str::to_string),
+            position: None,
+            internal_query,
+            r#where: error.r#where().map(str::to_string),
+            schema: error.schema().map(str::to_string),
+            table: error.table().map(str::to_string),
+            column: error.column().map(str::to_string),
+            data_type: error.data_type().map(str::to_string),
+            constraint: error.constraint().map(str::to_string),
+            server_position,
+        }
+    }
+}
+
+impl From<String> for QueryError {
+    fn from(error: String) -> Self {
+        QueryError::Other(error)
+    }
+}
+
+impl Database {
+    pub async fn new(options: PgConnectOptions, timeouts: QueryTimeouts) -> Result<Self, String> {
+        let pool = PgPoolOptions::new().connect_with(options).await;
+
+        if let Ok(pool) = pool {
+            return Ok(Self { pool, types: Arc::new(Mutex::new(decode::TypeCache::new())), timeouts });
+        } else if let Err(e) = pool {
+            return Err(e.to_string());
+        }
+
+        Err(String::from("Unknown error"))
+    }
+
+    /// Timeouts of the server the database belongs to.
+    pub fn timeouts(&self) -> QueryTimeouts {
+        self.timeouts
+    }
+
+    /// TLS of a pool connection, `None` if it is not encrypted.
+    pub async fn tls_state(&self) -> Result<Option<TlsState>, String> {
+        let row = sqlx::query("SELECT version, cipher FROM pg_stat_ssl WHERE pid = pg_backend_pid() AND ssl")
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        row.map(|row| {
+            Ok(TlsState {
+                version: row.try_get("version").map_err(|e| e.to_string())?,
+                cipher: row.try_get("cipher").map_err(|e| e.to_string())?,
+            })
+        })
+        .transpose()
+    }
+
+    pub async fn get_tables(&self) -> Result<Vec<Table>, String> {
+        let rows = sqlx::query("SELECT schemaname::text, tablename::text FROM pg_tables WHERE schemaname NOT IN ('pg_catalog', 'information_schema') AND schemaname NOT LIKE 'pg_%'")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        let tables = rows
+            .into_iter()
+            .map(|row| Ok(Table {
+                schema: row.try_get("schemaname").map_err(|e| e.to_string())?,
+                name: row.try_get("tablename").map_err(|e| e.to_string())?,
+            }))
+            .collect::<Result<Vec<Table>, String>>()?;
+
+        Ok(tables)
+    }
+
+    pub async fn get_databases(&self) -> Result<Vec<String>, String> {
+        let rows = sqlx::query("SELECT datname FROM pg_database")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        let databases = rows
+            .into_iter()
+            .map(|row| row.try_get("datname").map_err(|e| e.to_string()))
+            .collect::<Result<Vec<String>, String>>()?;
+
+        Ok(databases)
+    }
+
+    /// Runs the query in the session and passes decoded rows to `on_rows` in batches as they arrive, stopping after `max_rows` rows.
+    /// Row returning queries are read through a server-side cursor, which is returned while it has rows left.
+    /// Placeholders of the query are bound to `values`.
+    pub async fn execute_query(
+        &self,
+        query: &str,
+        values: &ParameterValues,
+        options: QueryOptions,
+        session: &Session,
+        mut on_rows: impl FnMut(&[ColumnDescription], Vec<Vec<ValueType>>) + Send,
+    ) -> Result<QueryOutcome, QueryError> {
+        let mut session_connection = session.connection.lock().await;
+        let result = match session.connect(self, &mut session_connection).await {
+            Ok(connection) => self.run_in_session(query, values, options, session, connection, &mut on_rows).await,
+            Err(e) => Err(QueryError::Other(e)),
+        };
+
+        session.update_transaction_status(&mut session_connection).await;
+
+        result
+    }
+
+    async fn run_in_session(
+        &self,
+        query: &str,
+        values: &ParameterValues,
+        options: QueryOptions,
+        session: &Session,
+        session_connection: &mut session::SessionConnection,
+        on_rows: &mut (impl FnMut(&[ColumnDescription], Vec<Vec<ValueType>>) + Send),
+    ) -> Result<QueryOutcome, QueryError> {
+        let timeouts = options.timeouts.unwrap_or(self.timeouts);
+
+        // The cursor of the previous query won't be read anymore
+        session.end_cursor_transaction(session_connection).await?;
+
+        let sql_ascii = session_connection.sql_ascii;
+        let connection = &mut session_connection.connection;
+        let status = session.transaction_status();
+
+        if status == TransactionStatus::Aborted {
+            // Nothing but the end of the transaction block runs in it, not even setting the timeouts,
+            // and no rows come back to be shown in a time zone
+            let results = connection.fetch_many(sqlx::query(query).persistent(false));
+            let (_, rows_affected) = self.stream_rows(results, &mut Vec::new(), decode::Formatting { time_zone: decode::TimeZone::Utc, money_digits: 2 }, timeouts, Some(options.max_rows), on_rows)
+                .await
+                .map_err(|e| e.locate(&script::ReplacedStatement::unchanged(query), sql_ascii))?;
+
+            return Ok(QueryOutcome { cursor: None, rows_affected, in_aborted_transaction: true });
+        }
+
+        let formatting = Self::get_formatting(connection, options.display_time_zone).await?;
+        let timeouts = Self::apply_timeouts(session_connection, timeouts, status == TransactionStatus::Open).await?;
+        let connection = &mut session_connection.connection;
+        let (query, arguments) = Self::bind_parameters(connection, query, values, status == TransactionStatus::Open).await?;
+
+        if is_row_query(&query.text) {
+            let declared = if status == TransactionStatus::Open {
+                Self::close_cursor(connection).await?;
+
+                Self::set_savepoint(connection).await?;
+                let declared = sqlx::query_with(&declare_cursor(&query.text), arguments.clone()).persistent(false).execute(&mut *connection).await.is_ok();
+                Self::release_savepoint(connection, !declared).await?;
+
+                declared
+            } else {
+                // Cursors only live inside of a transaction block
+                Self::execute(connection, "BEGIN").await?;
+                let declared = sqlx::query_with(&declare_cursor(&query.text), arguments.clone()).persistent(false).execute(&mut *connection).await.is_ok();
+                if !declared {
+                    Self::execute(connection, "ROLLBACK").await?;
+                }
+                session_connection.cursor_transaction = declared;
+
+                declared
+            };
+
+            // Statements that can't back a cursor (e.g. data-modifying CTEs) are run as they are
+            if declared {
+                let mut columns = Vec::new();
+                let has_more = self.read_cursor(&mut session_connection.connection, &mut columns, formatting, timeouts, Some(options.max_rows), on_rows).await?;
+
+                let held_transaction = session_connection.cursor_transaction;
+                if !has_more {
+                    session.end_cursor_transaction(session_connection).await?;
+                } else if held_transaction {
+                    session.hold_cursor_transaction();
+                }
+
+                let cursor = has_more.then(|| QueryCursor {
+                    session: session.clone(),
+                    timeouts,
+                    formatting,
+                    columns,
+                    held_transaction,
+                });
+
+                return Ok(QueryOutcome { cursor, rows_affected: 0, in_aborted_transaction: false });
+            }
+        }
+
+        let connection = &mut session_connection.connection;
+        let results = connection.fetch_many(sqlx::query_with(&query.text, arguments).persistent(false));
+        let (_, rows_affected) = self.stream_rows(results, &mut Vec::new(), formatting, timeouts, Some(options.max_rows), on_rows)
+            .await
+            .map_err(|e| e.locate(&query, sql_ascii))?;
+
+        Ok(QueryOutcome { cursor: None, rows_affected, in_aborted_transaction: false })
+    }
+
+    /// Numbers `:name` placeholders with the numbers left free by `$1` ones and binds the values as text.
+    /// Every placeholder is cast to the type the server infers for it, since text values can't be bound to other types.
+    /// Placeholders are left uncast if the server can't infer their types, binding them as text.
+    async fn bind_parameters(connection: &mut PgConnection, query: &str, values: &ParameterValues, in_transaction: bool) -> Result<(script::ReplacedStatement, PgArguments), String> {
+        let parameters = script::find_parameters(query);
+        let mut arguments = PgArguments::default();
+
+        if parameters.is_empty() {
+            return Ok((script::ReplacedStatement::unchanged(query), arguments));
+        }
+
+        // The run reports a statement the server can't describe
+        let (numbers, type_names) = Self::infer_parameter_types(connection, query, &parameters, in_transaction).await?;
+        let type_names = type_names.unwrap_or_default();
+
+        let query = script::replace_parameters(query, &parameters, |parameter| {
+            let number = numbers[parameter];
+
+            match type_names.get(number as usize - 1) {
+                Some(Some(type_name)) => format!("CAST(${} AS {})", number, type_name),
+                _ => format!("${}", number),
+            }
+        });
+
+        let count = numbers.values().copied().max().unwrap_or_default();
+        for number in 1..=count {
+            let value = numbers
+                .iter()
+                .find(|(_, parameter_number)| **parameter_number == number)
+                .and_then(|(parameter, _)| values.get(parameter).cloned().flatten());
+
+            arguments.add(value).map_err(|e| e.to_string())?;
+        }
+
+        Ok((query, arguments))
+    }
+
+    /// Writes the values into the query as literals cast to the types the server infers for the placeholders,
+    /// for statements such as `COPY` that can't take bound parameters.
+    /// The query is described even without placeholders, so mistakes in it are reported before it runs.
+    async fn inline_parameters(connection: &mut PgConnection, query: &str, values: &ParameterValues, in_transaction: bool) -> Result<String, String> {
+        let parameters = script::find_parameters(query);
+        let (numbers, type_names) = Self::infer_parameter_types(connection, query, &parameters, in_transaction).await?;
+        let type_names = type_names.map_err(copy::copy_error)?;
+
+        let query = script::replace_parameters(query, &parameters, |parameter| {
+            let literal = match values.get(parameter).cloned().flatten() {
+                // E-strings read the same whatever standard_conforming_strings is
+                Some(value) => format!("E'{}'", value.replace('\\', "\\\\").replace('\'', "''")),
+                None => String::from("NULL"),
+            };
+
+            match type_names.get(numbers[parameter] as usize - 1) {
+                Some(Some(type_name)) => format!("CAST({} AS {})", literal, type_name),
+                _ => literal,
+            }
+        });
+
+        Ok(query.text)
+    }
+
+    /// Numbers the placeholders and asks the server for their types, `None` where it can't infer one.
+    /// The types are the error of the server if it can't describe the query, which doesn't abort a transaction block.
+    async fn infer_parameter_types(
+        connection: &mut PgConnection,
+        query: &str,
+        parameters: &[(std::ops::Range<usize>, script::Parameter)],
+        in_transaction: bool,
+    ) -> Result<(HashMap<script::Parameter, u32>, Result<Vec<Option<String>>, sqlx::Error>), String> {
+        let used_numbers = parameters
+            .iter()
+            .filter_map(|(_, parameter)| match parameter {
+                script::Parameter::Positional(number) => Some(*number),
+                script::Parameter::Named(_) => None,
+            })
+            .collect::<HashSet<u32>>();
+
+        let mut numbers = HashMap::new();
+        let mut free_numbers = (1..).filter(|number| !used_numbers.contains(number));
+        for (_, parameter) in parameters {
+            if !numbers.contains_key(parameter) {
+                let number = match parameter {
+                    script::Parameter::Positional(number) => *number,
+                    script::Parameter::Named(_) => free_numbers.next().unwrap_or_default(),
+                };
+                numbers.insert(parameter.clone(), number);
+            }
+        }
+
+        let numbered_query = script::replace_parameters(query, parameters, |parameter| format!("${}", numbers[parameter])).text;
+        if in_transaction {
+            Self::set_savepoint(connection).await?;
+        }
+        let description = connection.describe(&numbered_query).await;
+        if in_transaction {
+            Self::release_savepoint(connection, description.is_err()).await?;
+        }
+        let description = match description {
+            Ok(description) => description,
+            Err(e) => return Ok((numbers, Err(e))),
+        };
+
+        let type_oids = match description.parameters() {
+            Some(Either::Left(types)) => types.iter().map(|type_info| type_info.oid().unwrap_or(Oid(0))).collect(),
+            _ => Vec::new(),
+        };
+
+        let type_names: Vec<Option<String>> = sqlx::query_scalar(
+            "SELECT CASE WHEN t.oid <> 0 THEN format_type(t.oid, NULL) END FROM UNNEST($1::oid[]) WITH ORDINALITY AS t(oid, number) ORDER BY t.number"
+        )
+            .bind(&type_oids)
+            .fetch_all(&mut *connection)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        Ok((numbers, Ok(type_names)))
+    }
+
+    /// Reads up to `count` more rows from the cursor, or all of them if `count` is `None`.
+    pub async fn fetch_more(
+        &self,
+        mut cursor: QueryCursor,
+        count: Option<u32>,
+        mut on_rows: impl FnMut(&[ColumnDescription], Vec<Vec<ValueType>>) + Send,
+    ) -> Result<Option<QueryCursor>, QueryError> {
+        let session = cursor.session.clone();
+        let mut session_connection = session.connection.lock().await;
+
+        let result = match session_connection.as_mut() {
+            Some(_) if cursor.is_expired() => Err(QueryError::Other(String::from("The cursor was closed after it was left unread"))),
+            Some(session_connection) => {
+                let has_more = self.read_cursor(&mut session_connection.connection, &mut cursor.columns, cursor.formatting, cursor.timeouts, count, &mut on_rows).await;
+
+                match has_more {
+                    Ok(false) => session.end_cursor_transaction(session_connection).await.map(|_| false).map_err(QueryError::from),
+                    Ok(true) if cursor.held_transaction => {
+                        session.hold_cursor_transaction();
+                        Ok(true)
+                    },
+                    has_more => has_more,
+                }
+            },
+            None => Err(QueryError::Other(String::from("The session was disconnected"))),
+        };
+
+        session.update_transaction_status(&mut session_connection).await;
+
+        Ok(result?.then_some(cursor))
+    }
+
+    /// Closes the session connection and opens a new one.
+    pub async fn reconnect_session(&self, session: &Session) -> Result<(), String> {
+        let mut session_connection = session.connection.lock().await;
+
+        session.disconnect(&mut session_connection).await;
+        session.connect(self, &mut session_connection).await?;
+
+        Ok(())
+    }
+
+    /// Fetches up to `count` rows from the cursor, or all of them if `count` is `None`.
+    /// Closes the cursor and returns `false` once it has no rows left.
+    async fn read_cursor(
+        &self,
+        connection: &mut PgConnection,
+        columns: &mut Vec<ColumnDescription>,
+        formatting: decode::Formatting,
+        timeouts: QueryTimeouts,
+        count: Option<u32>,
+        on_rows: &mut (impl FnMut(&[ColumnDescription], Vec<Vec<ValueType>>) + Send),
+    ) -> Result<bool, QueryError> {
+        let fetch = match count {
+            Some(count) => format!("FETCH FORWARD {} FROM {}", count, CURSOR_NAME),
+            None => format!("FETCH ALL FROM {}", CURSOR_NAME),
+        };
+
+        // The same FETCH returns different columns for every cursor, so its description can't be cached
+        let results = connection.fetch_many(sqlx::query(&fetch).persistent(false));
+        let (fetched, _) = self.stream_rows(results, columns, formatting, timeouts, None, on_rows).await?;
+
+        if count.is_some_and(|count| fetched == count) {
+            return Ok(true);
+        }
+
+        Self::execute(connection, &format!("CLOSE {}", CURSOR_NAME)).await?;
+
+        Ok(false)
+    }
+
+    /// Closes the cursor left in the transaction by a previous query that wasn't read to the end.
+    async fn close_cursor(connection: &mut PgConnection) -> Result<(), String> {
+        let is_open: bool = sqlx::query_scalar("SELECT EXISTS (SELECT FROM pg_cursors WHERE name = $1)")
+            .bind(CURSOR_NAME)
+            .fetch_one(&mut *connection)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        if is_open {
+            Self::execute(connection, &format!("CLOSE {}", CURSOR_NAME)).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Sets a savepoint so a failing statement doesn't abort the transaction of the user.
+    async fn set_savepoint(connection: &mut PgConnection) -> Result<(), String> {
+        Self::execute(connection, &format!("SAVEPOINT {}", CURSOR_NAME)).await
+    }
+
+    /// Releases the savepoint, rolling back to it first if the statement after it failed.
+    async fn release_savepoint(connection: &mut PgConnection, rollback: bool) -> Result<(), String> {
+        if rollback {
+            Self::execute(connection, &format!("ROLLBACK TO SAVEPOINT {}", CURSOR_NAME)).await?;
+        }
+
+        Self::execute(connection, &format!("RELEASE SAVEPOINT {}", CURSOR_NAME)).await
+    }
+
+    async fn execute(connection: &mut PgConnection, statement: &str) -> Result<(), String> {
+        sqlx::query(statement)
+            .execute(connection)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        Ok(())
+    }
+
+    /// Asks the server to cancel the query currently run by the backend.
+    /// Returns whether the backend was signalled, which fails if it's gone or belongs to another role.
+    pub async fn cancel_query(&self, pid: i32) -> Result<bool, String> {
+        sqlx::query_scalar("SELECT pg_cancel_backend($1)")
+            .bind(pid)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    async fn get_backend_pid(connection: &mut PgConnection) -> Result<i32, String> {
+        sqlx::query_scalar("SELECT pg_backend_pid()")
+            .fetch_one(connection)
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    /// Gives the session the timeouts, leaving alone the settings the user changed with `SET`.
+    /// Inside of a transaction block changed timeouts are set for the block only, since a rollback would revert them anyway.
+    /// Returns the timeouts in effect, 0 for the ones the user owns since their values aren't known in seconds.
+    async fn apply_timeouts(session_connection: &mut session::SessionConnection, timeouts: QueryTimeouts, in_transaction: bool) -> Result<QueryTimeouts, String> {
+        let (current_statement_timeout, current_lock_timeout): (String, String) =
+            sqlx::query_as("SELECT current_setting('statement_timeout'), current_setting('lock_timeout')")
+                .fetch_one(&mut session_connection.connection)
+                .await
+                .map_err(|e| e.to_string())?;
+
+        let mut applied = timeouts;
+        let settings = [
+            ("statement_timeout", timeouts.statement_timeout, current_statement_timeout, &mut session_connection.statement_timeout, &mut applied.statement_timeout),
+            ("lock_timeout", timeouts.lock_timeout, current_lock_timeout, &mut session_connection.lock_timeout, &mut applied.lock_timeout),
+        ];
+
+        for (name, seconds, current, setting, applied_seconds) in settings {
+            // Values set for a transaction block are gone once it ended
+            if !in_transaction {
+                setting.local = None;
+            }
+
+            let expected = setting.local.as_ref().or(setting.applied.as_ref());
+            if expected.is_some_and(|(_, value)| *value != current) {
+                setting.user_set = true;
+            }
+            if setting.user_set {
+                *applied_seconds = 0;
+                continue;
+            }
+            if expected.is_some_and(|(expected_seconds, _)| *expected_seconds == seconds) {
+                continue;
+            }
+
+            let value: String = sqlx::query_scalar("SELECT set_config($1, $2, $3)")
+                .bind(name)
+                .bind(format!("{}s", seconds))
+                .bind(in_transaction)
+                .fetch_one(&mut session_connection.connection)
+                .await
+                .map_err(|e| e.to_string())?;
+
+            if in_transaction {
+                setting.local = Some((seconds, value));
+            } else {
+                setting.applied = Some((seconds, value));
+            }
+        }
+
+        Ok(applied)
+    }
+
+    async fn get_formatting(connection: &mut PgConnection, display_time_zone: DisplayTimeZone) -> Result<decode::Formatting, String> {
+        // MONEY cast to NUMERIC keeps as many digits as the session shows
+        let (session_time_zone, money_digits): (String, i32) = sqlx::query_as("SELECT current_setting('TimeZone'), scale(0::money::numeric)")
+            .fetch_one(connection)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        let time_zone = match display_time_zone {
+            DisplayTimeZone::Utc => decode::TimeZone::Utc,
+            DisplayTimeZone::Local => decode::TimeZone::Local,
+            DisplayTimeZone::Session => decode::TimeZone::from_session(&session_time_zone),
+        };
+
+        Ok(decode::Formatting { time_zone, money_digits: money_digits.max(0) as u32 })
+    }
+
+    /// Decodes rows from the stream in page sized batches, describing the columns by the first row.
+    /// Returns the number of rows read and the number of rows affected.
+    async fn stream_rows(
+        &self,
+        mut results: BoxStream<'_, Result<Either<PgQueryResult, PgRow>, sqlx::Error>>,
+        columns: &mut Vec<ColumnDescription>,
+        formatting: decode::Formatting,
+        timeouts: QueryTimeouts,
+        limit: Option<u32>,
+        on_rows: &mut (impl FnMut(&[ColumnDescription], Vec<Vec<ValueType>>) + Send),
+    ) -> Result<(u32, u64), QueryError> {
+        let mut batch = Vec::with_capacity(ROWS_PER_PAGE as usize);
+        let mut count = 0;
+        let mut rows_affected = 0;
+
+        while let Some(result) = results.try_next().await.map_err(|e| QueryError::from_sqlx(e, timeouts))? {
+            let row = match result {
+                Either::Left(result) => {
+                    rows_affected += result.rows_affected();
+                    continue;
+                },
+                Either::Right(row) => row,
+            };
+
+            // Rows past the limit are read but not kept, so the results of the statement still arrive
+            if limit.is_some_and(|limit| count >= limit) {
+                continue;
+            }
+
+            if count == 0 && columns.is_empty() {
+                *columns = self.describe_columns(row.columns()).await?;
+            }
+
+            batch.push(row);
+            count += 1;
+
+            if batch.len() == ROWS_PER_PAGE as usize {
+                on_rows(columns, self.decode_rows(&batch, formatting));
+                batch.clear();
+            }
+        }
+
+        if !batch.is_empty() {
+            on_rows(columns, self.decode_rows(&batch, formatting));
+        }
+
+        Ok((count, rows_affected))
+    }
+
+    async fn describe_columns(&self, columns: &[PgColumn]) -> Result<Vec<ColumnDescription>, String> {
+        let type_oids = columns
+            .iter()
+            .filter_map(|column| column.type_info().oid())
+            .map(|oid| oid.0);
+
+        self.resolve_types(type_oids).await?;
+
+        let not_null = self.get_not_null_columns(columns).await?;
+        let types = self.types.lock().unwrap();
+
+        let columns = columns
+            .iter()
+            .map(|column| {
+                let type_oid = column.type_info().oid().map_or(0, |oid| oid.0);
+                let type_name = types.get(&type_oid)
+                    .map(|description| description.name.clone())
+                    .unwrap_or_else(|| column.type_info().name().to_string());
+                let nullable = column.relation_id()
+                    .zip(column.relation_attribute_no())
+                    .map(|(relation_id, attribute_no)| !not_null.contains(&(relation_id.0, attribute_no)));
+
+                ColumnDescription { name: column.name().to_string(), type_oid, type_name, nullable }
+            })
+            .collect();
+
+        Ok(columns)
+    }
+
+    fn decode_rows(&self, rows: &[PgRow], formatting: decode::Formatting) -> Vec<Vec<ValueType>> {
+        let types = self.types.lock().unwrap();
+        let context = decode::Context { types: &types, formatting };
+
+        rows
+            .iter()
+            .map(|row| row.columns()
+                .iter()
+                .map(|column| row
+                    .try_get_raw(column.ordinal())
+                    .map(|raw| decode::value(column.type_info(), raw, &context))
+                    .unwrap_or_else(|e| ValueType::DecodeError(e.to_string())))
+                .collect())
+            .collect()
+    }
+
+    /// Returns the `(table oid, column number)` pairs of the given table columns declared as NOT NULL.
+    async fn get_not_null_columns(&self, columns: &[PgColumn]) -> Result<HashSet<(u32, i16)>, String> {
+        let (relation_ids, attribute_nos): (Vec<Oid>, Vec<i16>) = columns
+            .iter()
+            .filter_map(|column| column.relation_id().zip(column.relation_attribute_no()))
+            .unzip();
+
+        if relation_ids.is_empty() {
+            return Ok(HashSet::new());
+        }
+
+        let rows = sqlx::query(
+            r#"SELECT a.attrelid, a.attnum FROM pg_attribute a
+            JOIN UNNEST($1::oid[], $2::int2[]) AS c(relation_id, attribute_no)
+                ON a.attrelid = c.relation_id AND a.attnum = c.attribute_no
+            WHERE a.attnotnull"#
+        )
+            .bind(&relation_ids)
+            .bind(&attribute_nos)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        rows.into_iter()
+            .map(|row| {
+                let relation_id: Oid = row.try_get("attrelid").map_err(|e| e.to_string())?;
+                let attribute_no: i16 = row.try_get("attnum").map_err(|e| e.to_string())?;
+
+                Ok((relation_id.0, attribute_no))
+            })
+            .collect()
+    }
+
+    /// Looks up the given types and the types they are built from in `pg_type`.
+    /// Descriptions are cached for the lifetime of the connection pool.
+    async fn resolve_types(&self, type_oids: impl IntoIterator<Item = u32>) -> Result<(), String> {
+        let mut pending: HashSet<u32> = type_oids.into_iter().collect();
+
+        loop {
+            pending.retain(|oid| !self.types.lock().unwrap().contains_key(oid));
+            if pending.is_empty() {
+                return Ok(());
+            }
+
+            let oids = pending.drain().map(Oid).collect::<Vec<Oid>>();
+            let rows = sqlx::query(
+                r#"SELECT t.oid, t.typname::text AS name, t.typtype::text AS kind, t.typcategory::text AS category,
+                    t.typelem AS element, t.typbasetype AS base_type, r.rngsubtype AS subtype,
+                    ARRAY(
+                        SELECT a.attname::text FROM pg_attribute a
+                        WHERE a.attrelid = t.typrelid AND a.attnum > 0 AND NOT a.attisdropped
+                        ORDER BY a.attnum
+                    ) AS field_names,
+                    ARRAY(
+                        SELECT a.atttypid FROM pg_attribute a
+                        WHERE a.attrelid = t.typrelid AND a.attnum > 0 AND NOT a.attisdropped
+                        ORDER BY a.attnum
+                    ) AS field_types
+                FROM pg_type t
+                LEFT JOIN pg_range r ON r.rngtypid = t.oid
+                WHERE t.oid = ANY($1)"#
+            )
+                .bind(&oids)
+                .fetch_all(&self.pool)
+                .await
+                .map_err(|e| e.to_string())?;
+
+            let mut types = self.types.lock().unwrap();
+            for oid in &oids {
+                // Types that vanished from the catalog are remembered as unknown ones
+                types.entry(oid.0).or_insert(decode::TypeDescription {
+                    name: format!("oid {}", oid.0),
+                    kind: decode::TypeKind::Base,
+                });
+            }
+
+            for row in rows {
+                let oid: Oid = row.try_get("oid").map_err(|e| e.to_string())?;
+                let name: String = row.try_get("name").map_err(|e| e.to_string())?;
+                let kind: String = row.try_get("kind").map_err(|e| e.to_string())?;
+                let category: String = row.try_get("category").map_err(|e| e.to_string())?;
+                let element: Oid = row.try_get("element").map_err(|e| e.to_string())?;
+                let base_type: Oid = row.try_get("base_type").map_err(|e| e.to_string())?;
+                let subtype: Option<Oid> = row.try_get("subtype").map_err(|e| e.to_string())?;
+                let field_names: Vec<String> = row.try_get("field_names").map_err(|e| e.to_string())?;
+                let field_types: Vec<Oid> = row.try_get("field_types").map_err(|e| e.to_string())?;
+
+                let kind = match (kind.as_str(), subtype) {
+                    ("e", _) => decode::TypeKind::Enum,
+                    ("c", _) => decode::TypeKind::Composite(
+                        field_names.into_iter().zip(field_types.into_iter().map(|oid| oid.0)).collect()
+                    ),
+                    ("d", _) => decode::TypeKind::Domain(base_type.0),
+                    ("r", Some(subtype)) => decode::TypeKind::Range(subtype.0),
+                    _ if category == "A" && element.0 != 0 => decode::TypeKind::Array(element.0),
+                    _ => decode::TypeKind::Base,
+                };
+
+                let description = decode::TypeDescription { name, kind };
+                pending.extend(description.dependencies());
+                types.insert(oid.0, description);
+            }
+        }
+    }
+}
+
+/// Result of running a query, the rows themselves are passed to the `on_rows` callback.
+#[derive(Debug)]
+pub struct QueryOutcome {
+    /// Cursor with the rows left to fetch.
+    pub cursor: Option<QueryCursor>,
+    /// Rows inserted, updated or deleted by the statement.
+    pub rows_affected: u64,
+    /// The statement ran in a failed transaction block, so ending the block rolled it back.
+    pub in_aborted_transaction: bool,
+}
+
+/// Open server-side cursor of a query with rows left to fetch.
+/// The cursor lives in the session until it's read to the end or the next query runs there.
+pub struct QueryCursor {
+    session: Session,
+    timeouts: QueryTimeouts,
+    formatting: decode::Formatting,
+    columns: Vec<ColumnDescription>,
+    /// The cursor lives in a transaction the app opened for it, which expires when the cursor is left unread.
+    held_transaction: bool,
+}
+
+impl QueryCursor {
+    /// The transaction held for the cursor expired and was committed, closing the cursor.
+    pub fn is_expired(&self) -> bool {
+        self.held_transaction && self.session.cursor_expires_at().is_none()
+    }
+}
+
+impl std::fmt::Debug for QueryCursor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("QueryCursor").field("columns", &self.columns).finish()
+    }
+}
+
+fn declare_cursor(query: &str) -> String {
+    format!("DECLARE {} NO SCROLL CURSOR FOR {}", CURSOR_NAME, query.trim().trim_end_matches(';'))
+}
+
+/// Byte offset of the character at the position counted from 1, the end of the text if it's past it.
+fn char_offset(text: &str, position: usize) -> usize {
+    text.char_indices().nth(position.saturating_sub(1)).map_or(text.len(), |(offset, _)| offset)
+}
+
+/// Byte offset of the byte at the position counted from 1, moved back to the start of its character.
+fn byte_offset(text: &str, position: usize) -> usize {
+    let mut offset = position.saturating_sub(1).min(text.len());
+    while !text.is_char_boundary(offset) {
+        offset -= 1;
+    }
+
+    offset
+}
+
+/// Whether the statement returns rows and can be read through a cursor.
+fn is_row_query(query: &str) -> bool {
+    let mut rest = query.trim_start();
+
+    loop {
+        if let Some(comment) = rest.strip_prefix("--") {
+            rest = comment.split_once('\n').map_or("", |(_, rest)| rest).trim_start();
+        } else if let Some(comment) = rest.strip_prefix("/*") {
+            rest = comment.split_once("*/").map_or("", |(_, rest)| rest).trim_start();
+        } else if let Some(parenthesized) = rest.strip_prefix('(') {
+            rest = parenthesized.trim_start();
+        } else {
+            break;
+        }
+    }
+
+    let keyword = rest
+        .split(|c: char| !c.is_ascii_alphabetic())
+        .next()
+        .unwrap_or_default();
+
+    ["SELECT", "WITH", "VALUES", "TABLE"]
+        .iter()
+        .any(|row_keyword| keyword.eq_ignore_ascii_case(row_keyword))
+}
@@ -0,0 +1,547 @@
+use std::fmt;
+use std::iter::Peekable;
+use std::ops::Range;
+use std::str::CharIndices;
+
+enum State {
+    Code,
+    String { backslash_escapes: bool },
+    QuotedIdentifier,
+    DollarQuoted(String),
+    LineComment,
+    BlockComment(u32),
+}
+
+enum Token {
+    /// Character outside of literals and comments.
+    Code(usize, char),
+    /// Start of a string, quoted identifier or dollar-quoted literal.
+    Literal,
+}
+
+/// Walks a script the way the server lexer does, telling code from literals and comments.
+struct Scanner<'a> {
+    script: &'a str,
+    chars: Peekable<CharIndices<'a>>,
+    state: State,
+    previous: Option<char>,
+}
+
+impl<'a> Scanner<'a> {
+    fn new(script: &'a str) -> Self {
+        Self { script, chars: script.char_indices().peekable(), state: State::Code, previous: None }
+    }
+}
+
+impl Iterator for Scanner<'_> {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Token> {
+        while let Some((index, c)) = self.chars.next() {
+            let next = self.chars.peek().map(|(_, c)| *c);
+            let previous = self.previous.replace(c);
+
+            match &mut self.state {
+                State::Code => match c {
+                    '-' if next == Some('-') => {
+                        self.chars.next();
+                        self.state = State::LineComment;
+                    },
+                    '/' if next == Some('*') => {
+                        self.chars.next();
+                        self.state = State::BlockComment(1);
+                    },
+                    '\'' => {
+                        // `E` must be a word of its own, `date'...'` is a typed literal without escapes
+                        let mut before = self.script[..index].chars().rev();
+                        let backslash_escapes = before.next().is_some_and(|c| c.eq_ignore_ascii_case(&'e'))
+                            && !before.next().is_some_and(is_identifier_char);
+
+                        self.state = State::String { backslash_escapes };
+                        return Some(Token::Literal);
+                    },
+                    '"' => {
+                        self.state = State::QuotedIdentifier;
+                        return Some(Token::Literal);
+                    },
+                    '$' if !previous.is_some_and(is_identifier_char) => {
+                        let tag = self.script[index + 1..]
+                            .chars()
+                            .take_while(|c| c.is_alphanumeric() || *c == '_')
+                            .collect::<String>();
+
+                        let is_tag = !tag.starts_with(|c: char| c.is_ascii_digit())
+                            && self.script[index + 1 + tag.len()..].starts_with('$');
+
+                        if !is_tag {
+                            return Some(Token::Code(index, c));
+                        }
+
+                        for _ in 0..=tag.chars().count() {
+                            self.chars.next();
+                        }
+                        self.state = State::DollarQuoted(format!("${}$", tag));
+                        return Some(Token::Literal);
+                    },
+                    c => return Some(Token::Code(index, c)),
+                },
+                State::String { backslash_escapes } => match c {
+                    '\\' if *backslash_escapes => {
+                        self.chars.next();
+                    },
+                    // A doubled quote stays inside the literal
+                    '\'' if next == Some('\'') => {
+                        self.chars.next();
+                    },
+                    '\'' => self.state = State::Code,
+                    _ => {},
+                },
+                State::QuotedIdentifier => match c {
+                    '"' if next == Some('"') => {
+                        self.chars.next();
+                    },
+                    '"' => self.state = State::Code,
+                    _ => {},
+                },
+                State::DollarQuoted(tag) => {
+                    if self.script[index..].starts_with(tag.as_str()) {
+                        for _ in 1..tag.chars().count() {
+                            self.chars.next();
+                        }
+                        self.state = State::Code;
+                    }
+                },
+                State::LineComment => {
+                    if c == '\n' {
+                        self.state = State::Code;
+                    }
+                },
+                State::BlockComment(comment_depth) => {
+                    if c == '*' && next == Some('/') {
+                        self.chars.next();
+                        *comment_depth -= 1;
+                        if *comment_depth == 0 {
+                            self.state = State::Code;
+                        }
+                    } else if c == '/' && next == Some('*') {
+                        self.chars.next();
+                        *comment_depth += 1;
+                    }
+                },
+            }
+        }
+
+        None
+    }
+}
+
+/// Splits a script into statements by semicolons outside of literals, comments, parentheses
+/// and `BEGIN ATOMIC ... END` bodies, like psql does.
+/// Returns byte ranges of the statements without the semicolons and surrounding whitespace,
+/// parts of the script holding only comments are skipped.
+pub fn split_statements(script: &str) -> Vec<Range<usize>> {
+    let mut statements = Vec::new();
+    let mut start = 0;
+    let mut has_code = false;
+    let mut depth = 0u32;
+    let mut word = String::new();
+    let mut words_count = 0u32;
+    // Like psql, `BEGIN` and `CASE` past the first word of a statement open a block `END` closes
+    let mut begin_depth = 0u32;
+
+    for token in Scanner::new(script) {
+        if let Token::Code(_, c) = token {
+            if is_identifier_char(c) {
+                word.push(c);
+                has_code = true;
+                continue;
+            }
+        }
+
+        if word.starts_with(|c: char| c.is_alphabetic() || c == '_') {
+            words_count += 1;
+
+            if word.eq_ignore_ascii_case("BEGIN") || word.eq_ignore_ascii_case("CASE") {
+                if words_count > 1 {
+                    begin_depth += 1;
+                }
+            } else if word.eq_ignore_ascii_case("END") {
+                begin_depth = begin_depth.saturating_sub(1);
+            }
+        }
+        word.clear();
+
+        match token {
+            Token::Code(index, ';') if depth == 0 && begin_depth == 0 => {
+                if has_code {
+                    statements.push(trim(script, start..index));
+                }
+                start = index + 1;
+                has_code = false;
+                words_count = 0;
+            },
+            Token::Code(_, '(') => {
+                has_code = true;
+                depth += 1;
+            },
+            Token::Code(_, ')') => {
+                has_code = true;
+                depth = depth.saturating_sub(1);
+            },
+            Token::Code(_, c) if !c.is_whitespace() => has_code = true,
+            Token::Code(..) => {},
+            Token::Literal => has_code = true,
+        }
+    }
+
+    if has_code {
+        statements.push(trim(script, start..script.len()));
+    }
+
+    statements
+}
+
+/// Returns the statement the cursor at the given byte offset is in, or the one before it
+/// when the cursor is between statements.
+pub fn statement_at(script: &str, offset: usize) -> Option<Range<usize>> {
+    let statements = split_statements(script);
+
+    statements
+        .iter()
+        .rev()
+        .find(|statement| statement.start <= offset)
+        .or(statements.first())
+        .cloned()
+}
+
+/// Placeholder of a value in a statement.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Parameter {
+    /// `:name` placeholder, numbered when the statement runs.
+    Named(String),
+    /// `$1` placeholder.
+    Positional(u32),
+}
+
+impl fmt::Display for Parameter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Parameter::Named(name) => write!(f, ":{}", name),
+            Parameter::Positional(number) => write!(f, "${}", number),
+        }
+    }
+}
+
+/// Finds `:name` and `$1` placeholders outside of literals and comments,
+/// skipping `::` casts and the bounds of `[lower:upper]` array slices.
+/// Returns them with their byte ranges in the order they appear.
+pub fn find_parameters(statement: &str) -> Vec<(Range<usize>, Parameter)> {
+    let mut parameters = Vec::new();
+    let mut bracket_depth = 0u32;
+
+    for token in Scanner::new(statement) {
+        let Token::Code(index, c) = token else {
+            continue;
+        };
+
+        let previous = statement[..index].chars().next_back();
+        let rest = &statement[index + 1..];
+
+        match c {
+            '[' => bracket_depth += 1,
+            ']' => bracket_depth = bracket_depth.saturating_sub(1),
+            ':' if bracket_depth == 0 && previous != Some(':') && rest.starts_with(|c: char| c.is_alphabetic() || c == '_') => {
+                let name = rest
+                    .chars()
+                    .take_while(|c| c.is_alphanumeric() || *c == '_')
+                    .collect::<String>();
+
+                parameters.push((index..index + 1 + name.len(), Parameter::Named(name)));
+            },
+            '$' if !previous.is_some_and(is_identifier_char) => {
+                let digits = rest.chars().take_while(|c| c.is_ascii_digit()).count();
+
+                if let Ok(number) = rest[..digits].parse() {
+                    parameters.push((index..index + 1 + digits, Parameter::Positional(number)));
+                }
+            },
+            _ => {},
+        }
+    }
+
+    parameters
+}
+
+/// Distinct placeholders of the statements in the order they first appear.
+pub fn statement_parameters<'a>(statements: impl IntoIterator<Item = &'a str>) -> Vec<Parameter> {
+    let mut parameters = Vec::new();
+
+    for statement in statements {
+        for (_, parameter) in find_parameters(statement) {
+            if !parameters.contains(&parameter) {
+                parameters.push(parameter);
+            }
+        }
+    }
+
+    parameters
+}
+
+/// Statement with its placeholders replaced, remembering where they were.
+#[derive(Clone, Debug)]
+pub struct ReplacedStatement {
+    pub text: String,
+    /// Byte ranges of every replacement in `text` and of the placeholder it replaced in the statement.
+    replacements: Vec<(Range<usize>, Range<usize>)>,
+}
+
+impl ReplacedStatement {
+    /// Statement with nothing replaced.
+    pub fn unchanged(statement: &str) -> Self {
+        Self { text: statement.to_string(), replacements: Vec::new() }
+    }
+
+    /// Maps a byte offset in the text back to the statement,
+    /// offsets inside of a replacement map to the start of its placeholder.
+    pub fn original_offset(&self, offset: usize) -> usize {
+        let mut original_offset = offset;
+
+        for (replaced, original) in &self.replacements {
+            if offset < replaced.start {
+                break;
+            }
+            if offset < replaced.end {
+                return original.start;
+            }
+            original_offset = original.end + (offset - replaced.end);
+        }
+
+        original_offset
+    }
+}
+
+/// Replaces the placeholders found by `find_parameters` with the text given for each of them.
+pub fn replace_parameters(statement: &str, parameters: &[(Range<usize>, Parameter)], mut replacement: impl FnMut(&Parameter) -> String) -> ReplacedStatement {
+    let mut text = String::with_capacity(statement.len());
+    let mut replacements = Vec::with_capacity(parameters.len());
+    let mut end = 0;
+
+    for (range, parameter) in parameters {
+        text.push_str(&statement[end..range.start]);
+
+        let start = text.len();
+        text.push_str(&replacement(parameter));
+        replacements.push((start..text.len(), range.clone()));
+
+        end = range.end;
+    }
+    text.push_str(&statement[end..]);
+
+    ReplacedStatement { text, replacements }
+}
+
+/// Words put between `CREATE` and the kind of object that aren't part of the command tag.
+const CREATE_MODIFIERS: &[&str] = &[
+    "OR", "REPLACE", "UNIQUE", "TEMP", "TEMPORARY", "GLOBAL", "LOCAL", "UNLOGGED", "TRUSTED", "PROCEDURAL",
+    "RECURSIVE", "CONSTRAINT", "DEFAULT",
+];
+
+/// Kinds of objects named in `CREATE`, `ALTER` and `DROP` command tags, longer ones first.
+const OBJECT_KINDS: &[&str] = &[
+    "TEXT SEARCH CONFIGURATION", "TEXT SEARCH DICTIONARY", "TEXT SEARCH PARSER", "TEXT SEARCH TEMPLATE",
+    "FOREIGN DATA WRAPPER", "USER MAPPING", "OPERATOR CLASS", "OPERATOR FAMILY", "ACCESS METHOD",
+    "MATERIALIZED VIEW", "FOREIGN TABLE", "EVENT TRIGGER", "DEFAULT PRIVILEGES",
+    "AGGREGATE", "CAST", "COLLATION", "CONVERSION", "DATABASE", "DOMAIN", "EXTENSION", "FUNCTION", "GROUP",
+    "INDEX", "LANGUAGE", "OPERATOR", "OWNED", "POLICY", "PROCEDURE", "PUBLICATION", "ROLE", "ROUTINE", "RULE",
+    "SCHEMA", "SEQUENCE", "SERVER", "STATISTICS", "SUBSCRIPTION", "SYSTEM", "TABLE", "TABLESPACE", "TRANSFORM",
+    "TRIGGER", "TYPE", "USER", "VIEW",
+];
+
+/// Guesses the command tag the server completes the statement with, such as `UPDATE 10` or `CREATE TABLE`,
+/// since sqlx only hands out the number of rows it holds.
+/// The guess is made from the text of the statement, so it's best-effort: rules rewriting the statement
+/// or commands the server tags differently from how they are spelled can get another tag.
+/// `in_aborted_transaction` tells the statement ran in a failed transaction block, which any way of ending rolls back.
+pub fn command_tag(statement: &str, rows_affected: u64, in_aborted_transaction: bool) -> String {
+    let words = top_level_words(statement);
+
+    // The tag of a statement with CTEs is the one of its main statement
+    let command_index = match words.first().map(String::as_str) {
+        Some("WITH") => words
+            .iter()
+            .position(|word| ["SELECT", "INSERT", "UPDATE", "DELETE", "MERGE", "VALUES", "TABLE"].contains(&word.as_str()))
+            .unwrap_or(0),
+        _ => 0,
+    };
+    let words = &words[command_index.min(words.len())..];
+    let word = |index: usize| words.get(index).map(String::as_str).unwrap_or_default();
+
+    match word(0) {
+        "INSERT" => format!("INSERT 0 {}", rows_affected),
+        "VALUES" | "TABLE" => format!("SELECT {}", rows_affected),
+        command @ ("SELECT" | "UPDATE" | "DELETE" | "MERGE" | "COPY" | "FETCH" | "MOVE") => format!("{} {}", command, rows_affected),
+        command @ ("CREATE" | "ALTER" | "DROP") => {
+            let rest = words[1..]
+                .iter()
+                .skip_while(|word| command == "CREATE" && CREATE_MODIFIERS.contains(&word.as_str()))
+                .map(String::as_str)
+                .collect::<Vec<&str>>()
+                .join(" ");
+
+            let kind = OBJECT_KINDS
+                .iter()
+                .find(|kind| rest == **kind || rest.starts_with(&format!("{} ", kind)))
+                .map(|kind| match *kind {
+                    "USER" | "GROUP" => "ROLE",
+                    kind => kind,
+                });
+
+            match kind {
+                // Tables and materialized views filled by a query report the rows they got
+                Some("TABLE" | "MATERIALIZED VIEW") if command == "CREATE" && words.iter().any(|word| word == "AS") => {
+                    format!("SELECT {}", rows_affected)
+                },
+                Some(kind) => format!("{} {}", command, kind),
+                None => command.to_string(),
+            }
+        },
+        "COMMIT" | "END" if in_aborted_transaction && word(1) != "PREPARED" => String::from("ROLLBACK"),
+        "PREPARE" if in_aborted_transaction && word(1) == "TRANSACTION" => String::from("ROLLBACK"),
+        command @ ("COMMIT" | "ROLLBACK") if word(1) == "PREPARED" => format!("{} PREPARED", command),
+        "START" => String::from("START TRANSACTION"),
+        "END" => String::from("COMMIT"),
+        "ABORT" => String::from("ROLLBACK"),
+        "TRUNCATE" => String::from("TRUNCATE TABLE"),
+        "LOCK" => String::from("LOCK TABLE"),
+        "DECLARE" => String::from("DECLARE CURSOR"),
+        "CLOSE" => String::from("CLOSE CURSOR"),
+        "REFRESH" => String::from("REFRESH MATERIALIZED VIEW"),
+        "PREPARE" if word(1) == "TRANSACTION" => String::from("PREPARE TRANSACTION"),
+        command => command.to_string(),
+    }
+}
+
+/// Upper-cased words of the statement outside of literals, comments and parentheses.
+fn top_level_words(statement: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut depth = 0u32;
+
+    for token in Scanner::new(statement) {
+        match token {
+            Token::Code(_, c) if depth == 0 && (c.is_alphanumeric() || c == '_') => {
+                word.push(c.to_ascii_uppercase());
+                continue;
+            },
+            Token::Code(_, '(') => depth += 1,
+            Token::Code(_, ')') => depth = depth.saturating_sub(1),
+            _ => {},
+        }
+
+        if !word.is_empty() {
+            words.push(std::mem::take(&mut word));
+        }
+    }
+
+    if !word.is_empty() {
+        words.push(word);
+    }
+
+    words
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '$'
+}
+
+fn trim(script: &str, range: Range<usize>) -> Range<usize> {
+    let text = &script[range.clone()];
+    let start = range.start + (text.len() - text.trim_start().len());
+    let end = range.end - (text.len() - text.trim_end().len());
+
+    start..end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn statements(script: &str) -> Vec<&str> {
+        split_statements(script).into_iter().map(|range| &script[range]).collect()
+    }
+
+    fn parameters(statement: &str) -> Vec<String> {
+        find_parameters(statement).into_iter().map(|(_, parameter)| parameter.to_string()).collect()
+    }
+
+    #[test]
+    fn split_skips_literals_and_comments() {
+        assert_eq!(statements("select ';'; select 2"), ["select ';'", "select 2"]);
+        assert_eq!(statements("select $$;$$; select $tag$ $$; $tag$"), ["select $$;$$", "select $tag$ $$; $tag$"]);
+        assert_eq!(statements("select /* ; /* ; */ ; */ 1; -- ;\nselect 2"), ["select /* ; /* ; */ ; */ 1", "-- ;\nselect 2"]);
+        assert_eq!(statements("select \"a;b\"; select (1;2)"), ["select \"a;b\"", "select (1;2)"]);
+        assert_eq!(statements("select 1;; -- comment\n; /* */"), ["select 1"]);
+    }
+
+    #[test]
+    fn split_handles_escapes() {
+        assert_eq!(statements(r"select E'\'; '; select 2"), [r"select E'\'; '", "select 2"]);
+        assert_eq!(statements(r"select e'\\'; select 2"), [r"select e'\\'", "select 2"]);
+        assert_eq!(statements("select 'it''s;'; select 2"), ["select 'it''s;'", "select 2"]);
+        // Typed literals ending with an `e` don't take backslash escapes
+        assert_eq!(statements(r"select date'2000-01-01\'; select time '12:00'"), [r"select date'2000-01-01\'", "select time '12:00'"]);
+    }
+
+    #[test]
+    fn split_keeps_begin_atomic_bodies() {
+        let script = "create function f() returns int language sql begin atomic select 1; select case when true then 2 end; end; select 3";
+        assert_eq!(statements(script), [&script[..script.len() - 10], "select 3"]);
+
+        assert_eq!(statements("begin; select 1; end; commit"), ["begin", "select 1", "end", "commit"]);
+        assert_eq!(statements("select case when true then 1 end; select 2"), ["select case when true then 1 end", "select 2"]);
+    }
+
+    #[test]
+    fn parameters_are_found_outside_of_literals() {
+        assert_eq!(parameters("select :a, $1, ':b', $$:c$$, \":d\" -- :e"), [":a", "$1"]);
+        assert_eq!(parameters("select :a::int, x::text, :_b"), [":a", ":_b"]);
+        assert_eq!(parameters("select arr[1:n], arr[:n], arr[f(:a):2], :b"), [":b"]);
+        assert_eq!(parameters("select $2 + $10"), ["$2", "$10"]);
+    }
+
+    #[test]
+    fn command_tags() {
+        assert_eq!(command_tag("insert into t values (1)", 1, false), "INSERT 0 1");
+        assert_eq!(command_tag("with x as (select 1) update t set a = 1", 3, false), "UPDATE 3");
+        assert_eq!(command_tag("create temp table t as select 1", 1, false), "SELECT 1");
+        assert_eq!(command_tag("create unique index i on t (a)", 0, false), "CREATE INDEX");
+        assert_eq!(command_tag("drop user u", 0, false), "DROP ROLE");
+        assert_eq!(command_tag("end", 0, false), "COMMIT");
+        assert_eq!(command_tag("commit prepared 'x'", 0, false), "COMMIT PREPARED");
+        assert_eq!(command_tag("rollback prepared 'x'", 0, false), "ROLLBACK PREPARED");
+        assert_eq!(command_tag("prepare transaction 'x'", 0, false), "PREPARE TRANSACTION");
+    }
+
+    #[test]
+    fn command_tags_of_nested_commands() {
+        assert_eq!(command_tag("with x as (select 1 a) insert into t select a from x", 1, false), "INSERT 0 1");
+        assert_eq!(command_tag("with recursive x(n) as (select 1 union all select n + 1 from x) select * from x", 5, false), "SELECT 5");
+        assert_eq!(command_tag("create table t (a int)", 0, false), "CREATE TABLE");
+        assert_eq!(command_tag("create table t (a int generated always as (1) stored)", 0, false), "CREATE TABLE");
+        assert_eq!(command_tag("create temp table t (a) as select 1", 1, false), "SELECT 1");
+        assert_eq!(command_tag("create materialized view v as select 1", 1, false), "SELECT 1");
+        assert_eq!(command_tag("create view v as select 1", 0, false), "CREATE VIEW");
+        assert_eq!(command_tag("select * into temp t from u", 2, false), "SELECT 2");
+        assert_eq!(command_tag("explain analyze update t set a = 1", 0, false), "EXPLAIN");
+        assert_eq!(command_tag("update t set a = 1 where false", 0, false), "UPDATE 0");
+    }
+
+    #[test]
+    fn ending_aborted_transaction_rolls_back() {
+        assert_eq!(command_tag("commit", 0, true), "ROLLBACK");
+        assert_eq!(command_tag("end", 0, true), "ROLLBACK");
+        assert_eq!(command_tag("prepare transaction 'x'", 0, true), "ROLLBACK");
+        assert_eq!(command_tag("rollback", 0, true), "ROLLBACK");
+        assert_eq!(command_tag("commit prepared 'x'", 0, true), "COMMIT PREPARED");
+    }
+}
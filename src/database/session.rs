@@ -0,0 +1,252 @@
+use sqlx::{ConnectOptions, Connection, Executor, Row};
+use sqlx_postgres::PgConnection;
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use super::Database;
+
+/// How long the transaction held open for a cursor waits for the cursor to be read again before it's committed.
+pub const CURSOR_TRANSACTION_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// State of the transaction of a session as the server reports it.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum TransactionStatus {
+    /// No transaction block is open, every statement commits on its own.
+    #[default]
+    Idle,
+    /// A transaction block is open and waits for `COMMIT` or `ROLLBACK`.
+    Open,
+    /// A statement failed inside the transaction block, only `ROLLBACK` can end it.
+    Aborted,
+}
+
+/// Dedicated connection of a SQL page, opened on first use and kept between runs
+/// so transactions, settings, temporary tables and prepared statements outlive a single run.
+/// Dropping the last handle closes the connection, which rolls back an open transaction.
+#[derive(Clone, Default)]
+pub struct Session {
+    pub(super) connection: Arc<tokio::sync::Mutex<Option<SessionConnection>>>,
+    state: Arc<Mutex<SessionState>>,
+}
+
+#[derive(Default)]
+struct SessionState {
+    pid: Option<i32>,
+    transaction_status: TransactionStatus,
+    error: Option<String>,
+    /// The connection was lost inside a transaction block, which the server rolled back.
+    lost_transaction: bool,
+    /// When the transaction held open for a cursor is committed.
+    cursor_expires_at: Option<Instant>,
+}
+
+pub(super) struct SessionConnection {
+    pub connection: PgConnection,
+    pub pid: i32,
+    /// The transaction block was opened by the app to read a cursor outside of a transaction of the user,
+    /// it's committed before the next statement runs.
+    pub cursor_transaction: bool,
+    /// The server encoding is `SQL_ASCII`, which counts error positions in bytes rather than characters.
+    pub sql_ascii: bool,
+    pub statement_timeout: TimeoutSetting,
+    pub lock_timeout: TimeoutSetting,
+}
+
+/// What the app knows about a timeout setting of the session, so it never overrides a value the user `SET`.
+#[derive(Default)]
+pub(super) struct TimeoutSetting {
+    /// Seconds the app set for the session and the value the server shows for them.
+    pub applied: Option<(u32, String)>,
+    /// Same for the current transaction block only, set when the timeouts changed inside of it.
+    pub local: Option<(u32, String)>,
+    /// The user changed the setting, the app leaves it alone until the session reconnects.
+    pub user_set: bool,
+}
+
+impl Session {
+    /// PID of the backend of the session, `None` until it's connected.
+    pub fn pid(&self) -> Option<i32> {
+        self.state.lock().unwrap().pid
+    }
+
+    pub fn transaction_status(&self) -> TransactionStatus {
+        self.state.lock().unwrap().transaction_status
+    }
+
+    /// Whether closing the session would lose uncommitted changes.
+    pub fn has_open_transaction(&self) -> bool {
+        self.transaction_status() != TransactionStatus::Idle
+    }
+
+    /// Why the session lost or couldn't open its connection.
+    pub fn error(&self) -> Option<String> {
+        self.state.lock().unwrap().error.clone()
+    }
+
+    /// Whether the transaction block of the user was rolled back when the connection was lost.
+    pub fn lost_transaction(&self) -> bool {
+        self.state.lock().unwrap().lost_transaction
+    }
+
+    /// When the transaction the app holds open for a cursor is committed unless the cursor is read again,
+    /// `None` if there is no such transaction.
+    pub fn cursor_expires_at(&self) -> Option<Instant> {
+        self.state.lock().unwrap().cursor_expires_at
+    }
+
+    /// Opens the connection with the options of the database pool unless it's open already.
+    pub(super) async fn connect<'a>(
+        &self,
+        database: &Database,
+        connection: &'a mut Option<SessionConnection>,
+    ) -> Result<&'a mut SessionConnection, String> {
+        if connection.is_none() {
+            let new_connection = Self::open(database).await;
+
+            let mut state = self.state.lock().unwrap();
+            match new_connection {
+                Ok(new_connection) => {
+                    *state = SessionState { pid: Some(new_connection.pid), ..Default::default() };
+                    *connection = Some(new_connection);
+                },
+                Err(e) => {
+                    state.error = Some(e.clone());
+                    return Err(e);
+                },
+            }
+        }
+
+        Ok(connection.as_mut().unwrap())
+    }
+
+    async fn open(database: &Database) -> Result<SessionConnection, String> {
+        let mut connection = database.pool.connect_options()
+            .connect()
+            .await
+            .map_err(|e| e.to_string())?;
+        let pid = Database::get_backend_pid(&mut connection).await?;
+        let sql_ascii = sqlx::query_scalar("SELECT current_setting('server_encoding') = 'SQL_ASCII'")
+            .fetch_one(&mut connection)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        Ok(SessionConnection {
+            connection,
+            pid,
+            cursor_transaction: false,
+            sql_ascii,
+            statement_timeout: TimeoutSetting::default(),
+            lock_timeout: TimeoutSetting::default(),
+        })
+    }
+
+    /// Closes the connection, rolling back an open transaction.
+    pub(super) async fn disconnect(&self, connection: &mut Option<SessionConnection>) {
+        if let Some(session_connection) = connection.take() {
+            // The server ends the session either way, closing only spares it an unexpected EOF
+            let _ = session_connection.connection.close().await;
+        }
+
+        *self.state.lock().unwrap() = SessionState::default();
+    }
+
+    /// Asks the server whether a transaction block is open after a statement was run.
+    /// A transaction opened for a cursor is reported as idle and rolled back if it failed, since the user never started it.
+    /// Only a connection that fails to answer is dropped, so the next run opens a new one.
+    /// A probe failing on the server, e.g. hit by a late cancel, is retried once and otherwise keeps the last status.
+    pub(super) async fn update_transaction_status(&self, connection: &mut Option<SessionConnection>) {
+        let Some(session_connection) = connection.as_mut() else {
+            return;
+        };
+
+        let mut retried = false;
+        let status = loop {
+            // `now()` is fixed when the transaction starts, while a simple query outside
+            // of a transaction block starts its own one at the same time as the statement
+            let result = session_connection.connection
+                .fetch_one("SELECT now() <> statement_timestamp()")
+                .await
+                .and_then(|row| row.try_get::<bool, _>(0));
+
+            match result {
+                Ok(true) if session_connection.cursor_transaction => break TransactionStatus::Idle,
+                Ok(true) => break TransactionStatus::Open,
+                Ok(false) => {
+                    session_connection.cursor_transaction = false;
+                    self.state.lock().unwrap().cursor_expires_at = None;
+                    break TransactionStatus::Idle;
+                },
+                Err(e) if e.as_database_error().and_then(|e| e.code()).is_some_and(|code| code == "25P02") => {
+                    if !session_connection.cursor_transaction {
+                        break TransactionStatus::Aborted;
+                    } else if let Err(e) = session_connection.connection.execute("ROLLBACK").await {
+                        return self.lose_connection(connection, e.to_string());
+                    } else {
+                        session_connection.cursor_transaction = false;
+                        self.state.lock().unwrap().cursor_expires_at = None;
+                        break TransactionStatus::Idle;
+                    }
+                },
+                // A failed probe aborts an open transaction block, which the retry then reports
+                Err(e) if e.as_database_error().is_some() && !retried => retried = true,
+                Err(e) if e.as_database_error().is_some() => return,
+                Err(e) => return self.lose_connection(connection, e.to_string()),
+            }
+        };
+
+        self.state.lock().unwrap().transaction_status = status;
+    }
+
+    /// Commits the transaction opened to read a cursor, which closes the cursor.
+    pub(super) async fn end_cursor_transaction(&self, session_connection: &mut SessionConnection) -> Result<(), String> {
+        if session_connection.cursor_transaction {
+            session_connection.connection.execute("COMMIT").await.map_err(|e| e.to_string())?;
+            session_connection.cursor_transaction = false;
+            self.state.lock().unwrap().cursor_expires_at = None;
+        }
+
+        Ok(())
+    }
+
+    /// Keeps the transaction opened for a cursor for `CURSOR_TRANSACTION_TIMEOUT` more,
+    /// so an unread cursor doesn't leave the backend idle in transaction, holding its locks and snapshot.
+    pub(super) fn hold_cursor_transaction(&self) {
+        let expires_at = Instant::now() + CURSOR_TRANSACTION_TIMEOUT;
+        let waiting = self.state.lock().unwrap().cursor_expires_at.replace(expires_at).is_some();
+
+        if !waiting {
+            tokio::spawn(self.clone().expire_cursor_transaction());
+        }
+    }
+
+    /// Commits the transaction held for a cursor once it expires, waiting again whenever the cursor was read meanwhile.
+    async fn expire_cursor_transaction(self) {
+        while let Some(expires_at) = self.cursor_expires_at() {
+            if expires_at > Instant::now() {
+                tokio::time::sleep_until(expires_at.into()).await;
+                continue;
+            }
+
+            let mut connection = self.connection.lock().await;
+            if self.cursor_expires_at().is_some_and(|expires_at| expires_at > Instant::now()) {
+                continue;
+            }
+
+            if let Some(session_connection) = connection.as_mut() {
+                if let Err(e) = self.end_cursor_transaction(session_connection).await {
+                    self.lose_connection(&mut connection, e);
+                }
+            }
+            self.state.lock().unwrap().cursor_expires_at = None;
+        }
+    }
+
+    pub(super) fn lose_connection(&self, connection: &mut Option<SessionConnection>, error: String) {
+        *connection = None;
+
+        let mut state = self.state.lock().unwrap();
+        let lost_transaction = state.transaction_status != TransactionStatus::Idle;
+        *state = SessionState { error: Some(error), lost_transaction, ..Default::default() };
+    }
+}
@@ -10,21 +10,29 @@ use egui::TopBottomPanel;
 use egui::{
     RichText, Modal, CentralPanel, Spinner, Layout, Align, TextEdit, Color32,
     Button, CollapsingHeader, Id, Grid, ScrollArea, Label,
-    Key, Slider,
+    Key, Slider, DragValue, KeyboardShortcut, Modifiers, Stroke, Sense,
 };
+use egui::text::{CCursor, CCursorRange};
 use egui_extras::{TableBuilder, Column};
-use egui_file_dialog::FileDialog;
+use egui_file_dialog::{DialogState, FileDialog};
 use log::{info, error};
 use std::fs as std_fs;
 use std::collections::HashMap;
 use std::io::Read;
 use std::io::Write;
+use std::ops::Range;
+use std::sync::atomic::Ordering;
 use std::sync::{Arc, Mutex};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use crate::utils::{encrypt_string, decrypt_string};
 use std::fs::File;
 use serde_json;
-use indexmap::IndexMap;
+
+/// How long the code that was run stays highlighted in the editor.
+const EXECUTED_HIGHLIGHT_DURATION: Duration = Duration::from_millis(800);
+
+/// Rows of a CSV file shown before it's imported.
+const PREVIEW_ROWS: usize = 10;
 
 
 struct DbManager {
@@ -36,6 +44,11 @@ pub struct Main<'a> {
     config: structs::Config,
     add_server_window: structs::AddServerWindow,
     delete_server_window: structs::DeleteServerWindow,
+    close_page_window: structs::ClosePageWindow,
+    parameters_window: structs::ParametersWindow,
+    import_window: structs::ImportWindow,
+    export_window: structs::ExportWindow,
+    result_export_window: structs::ResultExportWindow,
     edit_server_window: structs::EditServerWindow,
     sql_response_copy_window: structs::SQLResponseCopyWindow,
     settings_window: structs::SettingsWindow,
@@ -67,6 +80,11 @@ impl Main<'_> {
             config: structs::Config::default(),
             add_server_window: structs::AddServerWindow::default(),
             delete_server_window: structs::DeleteServerWindow::default(),
+            close_page_window: structs::ClosePageWindow::default(),
+            parameters_window: structs::ParametersWindow::default(),
+            import_window: structs::ImportWindow::default(),
+            export_window: structs::ExportWindow::default(),
+            result_export_window: structs::ResultExportWindow::default(),
             edit_server_window: structs::EditServerWindow::default(),
             sql_response_copy_window: structs::SQLResponseCopyWindow::default(),
             login_window: structs::LoginWindow::default(),
@@ -192,11 +210,7 @@ impl Main<'_> {
 
     async fn load_db(id: String, server: structs::Server, dbs: Arc<Mutex<HashMap<String, structs::DbState>>>) {
         info!("Starting to load database for server {}", server.ip);
-        let database_url = format!(
-            "postgres://{}:{}@{}:{}/{}",
-            server.user, server.password, server.ip, server.port, server.service_database
-        );
-        match database::Database::new(&database_url).await {
+        match database::Database::new(server.connect_options(&server.service_database), server.query_timeouts()).await {
             Ok(db) => {
                 info!("Database loaded for server {}", server.ip);
                 let databases_names = db.get_databases().await;
@@ -204,20 +218,19 @@ impl Main<'_> {
                 if let Ok(databases_names) = databases_names {
                     let mut databases: Vec<structs::LoadedDatabase> = Vec::new();
                     for name in databases_names {
-                        let database_url = format!(
-                            "postgres://{}:{}@{}:{}/{}",
-                            server.user, server.password, server.ip, server.port, name
-                        );
-                        let database = database::Database::new(&database_url).await;
+                        let database = database::Database::new(server.connect_options(&name), server.query_timeouts()).await;
                         if let Ok(database) = database {
                             let tables = database.get_tables().await;
 
                             if let Ok(tables) = tables {
+                                let tls = database.tls_state().await.unwrap_or_default();
+
                                 databases.push(
                                     structs::LoadedDatabase {
                                         name: name.clone(),
                                         database,
                                         tables,
+                                        tls,
                                     }
                                 );
                             } else if let Err(e) = tables {
@@ -244,64 +257,233 @@ impl Main<'_> {
         }
     }
 
-    fn get_sql_query_slice(result: &IndexMap<String, Vec<structs::ValueType>>, page_index: u32) -> IndexMap<String, Vec<structs::ValueType>> {
-        let total_rows = result.values().next().map_or(0, |v| v.len());
-        let pages_count = (total_rows as f32 / ROWS_PER_PAGE as f32).ceil() as u32;
+    async fn fetch_sql_query(sqlquery_page: structs::SQLQueryPage, display_time_zone: structs::DisplayTimeZone, max_rows: u32) {
+        let statements_count = sqlquery_page.statements.len();
 
-        let start_index = page_index as usize * ROWS_PER_PAGE as usize;
-        let end_index = if page_index == pages_count - 1 {
-            result[result.keys().next().unwrap()].len()
-        } else {
-            ((page_index + 1) * ROWS_PER_PAGE as u32) as usize
-        };
+        for (index, statement) in sqlquery_page.statements.iter().enumerate() {
+            {
+                let mut sql_query_execution_status = statement.sql_query_execution_status.lock().unwrap();
+                if !matches!(*sql_query_execution_status, structs::SQLQueryExecutionStatusType::Pending) {
+                    break;
+                }
+                *sql_query_execution_status = structs::SQLQueryExecutionStatusType::Running;
+            }
 
-        let mut current_page = IndexMap::new();
-        for (key, value) in result {
-            if start_index >= value.len() {
-                current_page.insert(key.clone(), vec![]);
-            } else {
-                let slice = value[start_index..end_index.min(value.len())].to_vec();
-                current_page.insert(key.clone(), slice);
+            let start_time = Instant::now();
+            let on_rows = Self::append_sql_query_rows(statement.sql_query_execution_status.clone(), start_time);
+            let options = database::QueryOptions { display_time_zone, max_rows, timeouts: sqlquery_page.timeouts };
+            let result = sqlquery_page.database.execute_query(&statement.code, &sqlquery_page.parameter_values, options, &sqlquery_page.session, on_rows).await;
+
+            // The next statement closes the cursor in the session, so only the last one keeps it
+            Self::finish_sql_query(result, start_time, statement, index + 1 == statements_count);
+
+            if !matches!(*statement.sql_query_execution_status.lock().unwrap(), structs::SQLQueryExecutionStatusType::Success(_)) {
+                break;
+            }
+        }
+
+        for statement in &sqlquery_page.statements {
+            let mut sql_query_execution_status = statement.sql_query_execution_status.lock().unwrap();
+            if let structs::SQLQueryExecutionStatusType::Pending = *sql_query_execution_status {
+                *sql_query_execution_status = structs::SQLQueryExecutionStatusType::Skipped;
             }
         }
-        current_page
     }
 
-    async fn fetch_sql_query(database: database::Database, code: &str, sql_query_execution_status: Option<Arc<Mutex<structs::SQLQueryExecutionStatusType>>>) {
+    async fn fetch_more_sql_query(sqlquery_page: structs::SQLQueryPage, statement: structs::SQLQueryStatement, query_cursor: database::QueryCursor, count: Option<u32>) {
         let start_time = Instant::now();
-        let result = database.execute_query(&code).await;
+        let on_rows = Self::append_sql_query_rows(statement.sql_query_execution_status.clone(), start_time);
+        let result = sqlquery_page.database.fetch_more(query_cursor, count, on_rows).await;
+
+        Self::finish_sql_query(result.map(|cursor| database::QueryOutcome { cursor, rows_affected: 0, in_aborted_transaction: false }), start_time, &statement, true);
+    }
+
+    fn append_sql_query_rows(sql_query_execution_status: Arc<Mutex<structs::SQLQueryExecutionStatusType>>, start_time: Instant) -> impl FnMut(&[structs::ColumnDescription], Vec<Vec<structs::ValueType>>) + Send {
+        move |columns, rows| {
+            let mut sql_query_execution_status = sql_query_execution_status.lock().unwrap();
+            match &mut *sql_query_execution_status {
+                structs::SQLQueryExecutionStatusType::Success(result) => result.result.rows.extend(rows),
+                structs::SQLQueryExecutionStatusType::Running => {
+                    *sql_query_execution_status = structs::SQLQueryExecutionStatusType::Success(structs::SQLQueryExecutionSuccess {
+                        result: structs::ResultSet { columns: columns.to_vec(), rows },
+                        pages_count: 0,
+                        rows_count: 0,
+                        rows_affected: 0,
+                        execution_time: 0,
+                        page_index: 0,
+                        fetching: true,
+                        has_more: false,
+                        plan: None,
+                        command_tag: None,
+                    });
+                },
+                _ => return,
+            }
+
+            if let structs::SQLQueryExecutionStatusType::Success(result) = &mut *sql_query_execution_status {
+                result.update_rows_count();
+                result.execution_time = start_time.elapsed().as_millis() as u64;
+            }
+        }
+    }
+
+    fn finish_sql_query(result: Result<database::QueryOutcome, database::QueryError>, start_time: Instant, statement: &structs::SQLQueryStatement, keep_cursor: bool) {
         let execution_time = start_time.elapsed().as_millis() as u64;
+        let mut sql_query_execution_status = statement.sql_query_execution_status.lock().unwrap();
 
-        let execution_status = match result {
-            Ok(result) => {
-                let pages_count = match result.is_empty() {
-                    true => 0,
-                    false => (result.values().next().unwrap().len() as f32 / ROWS_PER_PAGE as f32).ceil() as u32,
-                };
-                let rows_count = result.values().next().map_or(0, |v| v.len()) as u32;
+        // The cursor and whatever arrived after cancelling are dropped
+        if let structs::SQLQueryExecutionStatusType::Cancelled = *sql_query_execution_status {
+            return;
+        }
 
-                log::debug!("fetch_sql_query: rows_count={}, pages_count={}", rows_count, pages_count);
+        match result {
+            Ok(outcome) => {
+                if let structs::SQLQueryExecutionStatusType::Success(result) = &mut *sql_query_execution_status {
+                    result.fetching = false;
+                    result.has_more = outcome.cursor.is_some();
+                    result.rows_affected = outcome.rows_affected;
+                    result.execution_time = execution_time;
+                    result.plan = database::explain::QueryPlan::from_result(&result.result).map(Box::new);
+                    // The rows of a cursor are only all counted once it's read to the end
+                    let rows = outcome.rows_affected.max(result.rows_count as u64);
+                    result.command_tag = outcome.cursor.is_none().then(|| database::script::command_tag(&statement.code, rows, outcome.in_aborted_transaction));
+
+                    log::debug!("fetch_sql_query: rows_count={}, pages_count={}", result.rows_count, result.pages_count);
+                } else {
+                    *sql_query_execution_status = structs::SQLQueryExecutionStatusType::Success(structs::SQLQueryExecutionSuccess {
+                        result: structs::ResultSet::default(),
+                        pages_count: 0,
+                        rows_count: 0,
+                        rows_affected: outcome.rows_affected,
+                        execution_time,
+                        page_index: 0,
+                        fetching: false,
+                        has_more: false,
+                        plan: None,
+                        command_tag: Some(database::script::command_tag(&statement.code, outcome.rows_affected, outcome.in_aborted_transaction)),
+                    });
+                }
 
-                structs::SQLQueryExecutionStatusType::Success(structs::SQLQueryExecutionSuccess {
-                    result: result.clone(),
-                    current_page: Some(if pages_count > 0 {
-                        Self::get_sql_query_slice(&result, 0)
-                    } else {
-                        result
-                    }),
-                    pages_count: pages_count,
-                    rows_count: rows_count,
-                    execution_time: execution_time,
-                    page_index: 0,
-                })
+                if keep_cursor {
+                    *statement.cursor.lock().unwrap() = outcome.cursor;
+                }
             },
-            Err(e) => structs::SQLQueryExecutionStatusType::Error(e.to_string()),
+            // The cancel request can end the query before the statement is marked as cancelled
+            Err(e) if e.is_query_canceled() && statement.cancel_requested.load(Ordering::Relaxed) => {
+                *sql_query_execution_status = structs::SQLQueryExecutionStatusType::Cancelled;
+            },
+            Err(e) => *sql_query_execution_status = structs::SQLQueryExecutionStatusType::Error(e),
+        }
+    }
+
+    /// Runs the statements found in the byte range of the page code, or explains them if `explain` is given,
+    /// asking for the values of their placeholders first if they have any.
+    fn run_sql_query(runtime: &tokio::runtime::Runtime, settings: &structs::Settings, parameters_window: &mut structs::ParametersWindow, page_index: usize, sqlquery_page: &mut structs::SQLQueryPage, range: Range<usize>, explain: Option<database::explain::ExplainMode>) {
+        let code = &sqlquery_page.code[range.clone()];
+        let statements: Vec<(String, Option<Range<usize>>)> = database::script::split_statements(code)
+            .into_iter()
+            .map(|statement_range| {
+                let statement = match explain {
+                    Some(explain) => explain.wrap(&code[statement_range.clone()]),
+                    None => code[statement_range.clone()].to_string(),
+                };
+
+                (statement, Some(range.start + statement_range.start..range.start + statement_range.end))
+            })
+            .collect();
+
+        let parameters = database::script::statement_parameters(statements.iter().map(|(statement, _)| statement.as_str()));
+        if !parameters.is_empty() {
+            *parameters_window = structs::ParametersWindow {
+                show: true,
+                page_index: Some(page_index),
+                statements,
+                executed_range: Some(range),
+                values: Self::parameter_fields(sqlquery_page, parameters),
+                export: false,
+            };
+
+            return;
+        }
+
+        Self::run_statements(runtime, settings, sqlquery_page, statements);
+        sqlquery_page.executed_range = Some((range, Instant::now()));
+    }
+
+    /// Fields of the parameters window for the placeholders, filled with the values last given on the page.
+    fn parameter_fields(sqlquery_page: &structs::SQLQueryPage, parameters: Vec<database::script::Parameter>) -> Vec<(database::script::Parameter, String, bool)> {
+        parameters
+            .into_iter()
+            .map(|parameter| {
+                let value = sqlquery_page.parameter_values.get(&parameter).cloned();
+                let is_null = matches!(value, Some(None));
+
+                (parameter, value.flatten().unwrap_or_default(), is_null)
+            })
+            .collect()
+    }
+
+    /// Opens the export of the statement, run in the session of the page with the values of its placeholders.
+    fn export_statement(export_window: &mut structs::ExportWindow, sqlquery_page: &structs::SQLQueryPage, statement: String) {
+        *export_window = structs::ExportWindow {
+            show: true,
+            database: Some(sqlquery_page.database.clone()),
+            source: Some(database::copy::CopySource::Query {
+                statement,
+                session: sqlquery_page.session.clone(),
+                values: sqlquery_page.parameter_values.clone(),
+            }),
+            ..Default::default()
         };
+    }
 
-        if let Some(sql_query_execution_status) = sql_query_execution_status {
-            let mut sql_query_execution_status = sql_query_execution_status.lock().unwrap();
-            *sql_query_execution_status = execution_status;
+    /// Runs the statements with their byte ranges in the page code, if they come from it.
+    fn run_statements(runtime: &tokio::runtime::Runtime, settings: &structs::Settings, sqlquery_page: &mut structs::SQLQueryPage, statements: Vec<(String, Option<Range<usize>>)>) {
+        // The cursor of the previous run is closed in the session before the first statement runs
+        sqlquery_page.statements = statements
+            .into_iter()
+            .map(|(code, source_range)| structs::SQLQueryStatement {
+                code,
+                sql_query_execution_status: Arc::new(Mutex::new(structs::SQLQueryExecutionStatusType::Pending)),
+                cursor: Arc::new(Mutex::new(None)),
+                cancel_requested: Default::default(),
+                source_range,
+                error_located: false,
+            })
+            .collect();
+        sqlquery_page.selected_statement = 0;
+        sqlquery_page.executed_range = None;
+        sqlquery_page.error_position = None;
+        sqlquery_page.grid_selection = None;
+
+        let sqlquery_page_clone = sqlquery_page.clone();
+        let display_time_zone = settings.display_time_zone;
+        let max_rows = settings.max_rows;
+
+        runtime.spawn(async move {
+            Self::fetch_sql_query(sqlquery_page_clone, display_time_zone, max_rows).await;
+        });
+    }
+
+    /// Maps the byte offset of an error in the statement to the page code,
+    /// `None` if the statement doesn't come from the code or the code has changed since it ran.
+    fn error_offset(code: &str, statement: &structs::SQLQueryStatement, position: usize) -> Option<usize> {
+        let source_range = statement.source_range.clone()?;
+        if !code.get(source_range.clone()).is_some_and(|source| statement.code.ends_with(source)) {
+            return None;
         }
+
+        // Text put before the statement, such as EXPLAIN, isn't in the page code
+        let prefix_length = statement.code.len() - source_range.len();
+
+        Some(source_range.start + position.saturating_sub(prefix_length).min(source_range.len()))
+    }
+
+    /// Converts the editor selection in characters to a byte range of the code.
+    fn selection_byte_range(code: &str, selection: &Range<usize>) -> Range<usize> {
+        let byte_index = |char_index: usize| code.char_indices().nth(char_index).map_or(code.len(), |(index, _)| index);
+
+        byte_index(selection.start)..byte_index(selection.end)
     }
 
     fn save_code(sqlquery_page: &mut structs::SQLQueryPage) {
@@ -313,31 +495,16 @@ impl Main<'_> {
         file.write_all(sqlquery_page.code.as_bytes()).unwrap();
     }
 
-    fn export_output_to_csv(data: &IndexMap<String, Vec<structs::ValueType>>, file_path: String) {
-        let file = File::create(file_path).unwrap();
-        let mut wtr = csv::Writer::from_writer(file);
-
-        let headers: Vec<&str> = data.keys().map(|k| k.as_str()).collect();
-
-        wtr.write_record(&headers).unwrap();
-
-        if let Some(first_column) = data.values().next() {
-            let row_count = first_column.len();
-            for i in 0..row_count {
-                let mut record = Vec::with_capacity(data.len());
-
-                for col in data.values() {
-                    let value = col.get(i)
-                        .map(|v| v.to_string())
-                        .unwrap_or_default();
-                    record.push(value);
-                }
-
-                wtr.write_record(&record).unwrap();
-            }
-        }
+    /// Opens the save dialog with the file name filled in.
+    fn save_file_as(file_dialog: &mut FileDialog, file_name: &str) {
+        file_dialog.config_mut().default_file_name = file_name.to_string();
+        file_dialog.save_file();
+        file_dialog.config_mut().default_file_name.clear();
+    }
 
-        wtr.flush().unwrap();
+    /// Text of an optional form field, `None` if it's blank.
+    fn optional_field(field: &str) -> Option<String> {
+        Some(field.trim().to_string()).filter(|field| !field.is_empty())
     }
 
     async fn reload_server(index: usize, config: structs::Config, dbs: Arc<Mutex<HashMap<String, structs::DbState>>>) {
@@ -349,11 +516,7 @@ impl Main<'_> {
             dbs.remove(&id);
         }
 
-        let database_url = format!(
-            "postgres://{}:{}@{}:{}/{}",
-            server.user, server.password, server.ip, server.port, server.service_database
-        );
-        match database::Database::new(&database_url).await {
+        match database::Database::new(server.connect_options(&server.service_database), server.query_timeouts()).await {
             Ok(database) => {
                 let databases = database.get_databases().await;
                 match databases {
@@ -361,22 +524,19 @@ impl Main<'_> {
                         let mut loaded_databases: Vec<structs::LoadedDatabase> = Vec::new();
 
                         for db_name in databases {
-                            let db_url = format!(
-                                "postgres://{}:{}@{}:{}/{}",
-                                server.user, server.password, server.ip, server.port, db_name
-                            );
-
-                            match database::Database::new(&db_url).await {
+                            match database::Database::new(server.connect_options(&db_name), server.query_timeouts()).await {
                                 Ok(db_connection) => {
                                     let tables = match db_connection.get_tables().await {
                                         Ok(tables) => tables,
                                         Err(_) => Vec::new()
                                     };
+                                    let tls = db_connection.tls_state().await.unwrap_or_default();
 
                                     loaded_databases.push(structs::LoadedDatabase {
                                         name: db_name,
                                         database: db_connection,
                                         tables,
+                                        tls,
                                     });
                                 },
                                 Err(_) => {
@@ -384,6 +544,7 @@ impl Main<'_> {
                                         name: db_name,
                                         database: database.clone(),
                                         tables: Vec::new(),
+                                        tls: None,
                                     });
                                 }
                             }
@@ -424,8 +585,29 @@ impl Main<'_> {
                             ui.add(TextEdit::singleline(&mut self.add_server_window.name_field).background_color(input_color));
                             ui.end_row();
 
+                            ui.label(self.trans.connection_string());
+                            let parse_error = connection_string::ConnectionString::parse(&self.add_server_window.connection_string_field).err();
+                            let mut field = TextEdit::singleline(&mut self.add_server_window.connection_string_field)
+                                .hint_text("postgresql://user@host:5432/database");
+                            if parse_error.is_some() {
+                                field = field.text_color(Color32::from_rgb(255, 0, 0));
+                            }
+                            let mut response = ui.add(field.background_color(input_color));
+                            if let Some(e) = parse_error {
+                                response = response.on_hover_text(e);
+                            }
+                            // The string is read while it's edited and written back from the fields otherwise
+                            if response.changed() && !self.add_server_window.connection_string_field.trim().is_empty() {
+                                if let Ok(connection_string) = connection_string::ConnectionString::parse(&self.add_server_window.connection_string_field) {
+                                    self.add_server_window.apply_connection_string(connection_string);
+                                }
+                            } else if !response.has_focus() {
+                                self.add_server_window.connection_string_field = self.add_server_window.connection_uri();
+                            }
+                            ui.end_row();
+
                             ui.label(self.trans.server_address());
-                            ui.add(TextEdit::singleline(&mut self.add_server_window.ip_field).background_color(input_color));
+                            ui.add(TextEdit::singleline(&mut self.add_server_window.ip_field).hint_text(self.trans.server_address_hint()).background_color(input_color));
                             ui.end_row();
 
                             ui.label(self.trans.port());
@@ -448,6 +630,46 @@ impl Main<'_> {
                             ui.label(self.trans.service_database());
                             ui.add(TextEdit::singleline(&mut self.add_server_window.service_database_field).background_color(input_color));
                             ui.end_row();
+
+                            ui.label(self.trans.statement_timeout());
+                            let is_error = self.add_server_window.statement_timeout_field.parse::<u32>().is_err();
+                            let mut field = TextEdit::singleline(&mut self.add_server_window.statement_timeout_field);
+                            if is_error {
+                                field = field.text_color(Color32::from_rgb(255, 0, 0));
+                            }
+                            ui.add(field.background_color(input_color));
+                            ui.end_row();
+
+                            ui.label(self.trans.lock_timeout());
+                            let is_error = self.add_server_window.lock_timeout_field.parse::<u32>().is_err();
+                            let mut field = TextEdit::singleline(&mut self.add_server_window.lock_timeout_field);
+                            if is_error {
+                                field = field.text_color(Color32::from_rgb(255, 0, 0));
+                            }
+                            ui.add(field.background_color(input_color));
+                            ui.end_row();
+
+                            ui.label(self.trans.ssl_mode());
+                            egui::ComboBox::from_id_salt("add_server_ssl_mode")
+                                .selected_text(self.add_server_window.ssl_mode.to_string())
+                                .show_ui(ui, |ui| {
+                                    for ssl_mode in structs::SslMode::ALL {
+                                        ui.selectable_value(&mut self.add_server_window.ssl_mode, ssl_mode, ssl_mode.to_string());
+                                    }
+                                });
+                            ui.end_row();
+
+                            ui.label(self.trans.ssl_root_cert());
+                            ui.add(TextEdit::singleline(&mut self.add_server_window.ssl_root_cert_field).hint_text(self.trans.ssl_root_cert_hint()).background_color(input_color));
+                            ui.end_row();
+
+                            ui.label(self.trans.ssl_client_cert());
+                            ui.add(TextEdit::singleline(&mut self.add_server_window.ssl_client_cert_field).background_color(input_color));
+                            ui.end_row();
+
+                            ui.label(self.trans.ssl_client_key());
+                            ui.add(TextEdit::singleline(&mut self.add_server_window.ssl_client_key_field).background_color(input_color));
+                            ui.end_row();
                         });
 
                     let is_name_error = {
@@ -497,7 +719,24 @@ impl Main<'_> {
                         }
                     };
 
-                    let enable_save_button = !is_name_error && !is_ip_error && !is_port_error && !is_user_error && !is_service_database_error;
+                    let is_timeout_error = {
+                        if self.add_server_window.statement_timeout_field.parse::<u32>().is_err() || self.add_server_window.lock_timeout_field.parse::<u32>().is_err() {
+                            ui.label(self.trans.incorrect_timeout_value());
+                            true
+                        } else {
+                            false
+                        }
+                    };
+                    let is_ssl_error = {
+                        if self.add_server_window.ssl_client_cert_field.trim().is_empty() != self.add_server_window.ssl_client_key_field.trim().is_empty() {
+                            ui.label(self.trans.client_cert_and_key_are_required_together());
+                            true
+                        } else {
+                            false
+                        }
+                    };
+
+                    let enable_save_button = !is_name_error && !is_ip_error && !is_port_error && !is_user_error && !is_service_database_error && !is_timeout_error && !is_ssl_error;
 
                     ui.with_layout(Layout::top_down(Align::RIGHT), |ui| {
                         ui.separator();
@@ -511,6 +750,14 @@ impl Main<'_> {
                                     user: self.add_server_window.user_field.clone(),
                                     password: self.add_server_window.password_field.clone(),
                                     service_database: self.add_server_window.service_database_field.clone(),
+                                    statement_timeout: self.add_server_window.statement_timeout_field.parse::<u32>().unwrap(),
+                                    lock_timeout: self.add_server_window.lock_timeout_field.parse::<u32>().unwrap(),
+                                    ssl_mode: self.add_server_window.ssl_mode,
+                                    ssl_root_cert: Self::optional_field(&self.add_server_window.ssl_root_cert_field),
+                                    ssl_client_cert: Self::optional_field(&self.add_server_window.ssl_client_cert_field),
+                                    ssl_client_key: Self::optional_field(&self.add_server_window.ssl_client_key_field),
+                                    application_name: self.add_server_window.application_name.clone(),
+                                    options: self.add_server_window.options.clone(),
                                 };
                                 self.config.servers.push(server);
                                 self.save_config();
@@ -524,6 +771,471 @@ impl Main<'_> {
                 });
         }
 
+        if self.parameters_window.show {
+            Modal::new(Id::new("parameters_modal")).show(ctx, |ui| {
+                ui.set_width(400.0);
+
+                widgets::modal_label(ui, self.trans.parameters());
+
+                Grid::new("parameters_grid").num_columns(3).show(ui, |ui| {
+                    for (parameter, value, is_null) in &mut self.parameters_window.values {
+                        ui.label(RichText::new(parameter.to_string()).code());
+                        ui.add_enabled(!*is_null, TextEdit::singleline(value).desired_width(240.0));
+                        ui.checkbox(is_null, "NULL");
+                        ui.end_row();
+                    }
+                });
+
+                ui.with_layout(Layout::top_down(Align::RIGHT), |ui| {
+                    ui.separator();
+
+                    ui.horizontal(|ui| {
+                        let submit_text = match self.parameters_window.export {
+                            true => self.trans.export(),
+                            false => self.trans.run(),
+                        };
+                        if ui.button(submit_text).clicked() || ui.input(|i| i.key_pressed(Key::Enter)) {
+                            let parameters_window = std::mem::take(&mut self.parameters_window);
+                            let page = parameters_window.page_index.and_then(|idx| self.pages.pages.get_mut(idx));
+
+                            if let Some(structs::PageType::SQLQuery(sqlquery_page)) = page.map(|page| &mut page.page_type) {
+                                for (parameter, value, is_null) in parameters_window.values {
+                                    sqlquery_page.parameter_values.insert(parameter, (!is_null).then_some(value));
+                                }
+
+                                if parameters_window.export {
+                                    if let Some((statement, _)) = parameters_window.statements.into_iter().next() {
+                                        Self::export_statement(&mut self.export_window, sqlquery_page, statement);
+                                    }
+                                } else {
+                                    Self::run_statements(&self.runtime, &self.config.settings, sqlquery_page, parameters_window.statements);
+                                    sqlquery_page.executed_range = parameters_window.executed_range.map(|range| (range, Instant::now()));
+                                }
+                            }
+                        }
+                        if ui.button(self.trans.back()).clicked() {
+                            self.parameters_window = structs::ParametersWindow::default();
+                        }
+                    });
+                });
+            });
+        }
+
+        self.select_file_dialog.update(ctx);
+
+        if let Some(structs::SelectFileDialogAction::ImportCsv) = self.select_file_dialog_action {
+            if let Some(file_path) = self.select_file_dialog.take_picked() {
+                self.select_file_dialog_action = None;
+
+                self.import_window.file_path = Some(file_path);
+                self.import_window.preview = None;
+                self.import_window.show = true;
+            } else if matches!(self.select_file_dialog.state(), DialogState::Cancelled | DialogState::Closed) {
+                self.select_file_dialog_action = None;
+
+                // Keep the file chosen before, if any
+                self.import_window.show = self.import_window.file_path.is_some();
+            }
+        }
+
+        if self.import_window.show {
+            let import_window = &mut self.import_window;
+
+            if let (None, Some(file_path)) = (&import_window.preview, &import_window.file_path) {
+                import_window.preview = Some(database::copy::preview_csv(file_path, &import_window.options, PREVIEW_ROWS));
+                import_window.mapping.clear();
+            }
+
+            let table_columns = import_window.table_columns.lock().unwrap().clone();
+
+            // File columns go to the table columns of the same name, or in order without a header
+            if let (Some(Ok(preview)), Some(Ok(table_columns))) = (&import_window.preview, &table_columns) {
+                if import_window.mapping.len() != preview.columns.len() {
+                    import_window.mapping = preview.columns
+                        .iter()
+                        .enumerate()
+                        .map(|(index, name)| match import_window.options.header {
+                            true => table_columns.iter().find(|column| column.eq_ignore_ascii_case(name.trim())).cloned(),
+                            false => table_columns.get(index).cloned(),
+                        })
+                        .collect();
+                }
+            }
+
+            let running = import_window.progress.as_ref().is_some_and(|progress| progress.result().is_none());
+            if running {
+                ctx.request_repaint_after(Duration::from_millis(100));
+            }
+
+            Modal::new(Id::new("import_modal")).show(ctx, |ui| {
+                ui.set_width(640.0);
+
+                widgets::modal_label(ui, self.trans.import_data_into(&import_window.table));
+
+                ui.horizontal(|ui| {
+                    ui.label(self.trans.file());
+                    if let Some(file_path) = &import_window.file_path {
+                        ui.label(RichText::new(file_path.to_string_lossy()).code());
+                    }
+                    if ui.add_enabled(!running, Button::new(self.trans.choose_file())).clicked() {
+                        import_window.show = false;
+
+                        self.select_file_dialog_action = Some(structs::SelectFileDialogAction::ImportCsv);
+                        self.select_file_dialog.pick_file();
+                    }
+                });
+
+                ui.add_space(8.0);
+
+                let mut options = import_window.options.clone();
+
+                ui.add_enabled_ui(!running, |ui| {
+                    Grid::new("import_options").num_columns(4).show(ui, |ui| {
+                        ui.label(self.trans.delimiter());
+                        ui.add(TextEdit::singleline(&mut import_window.delimiter_field).desired_width(40.0));
+                        ui.label(self.trans.quote());
+                        ui.add(TextEdit::singleline(&mut import_window.quote_field).desired_width(40.0));
+                        ui.end_row();
+
+                        ui.label(self.trans.csv_null());
+                        ui.add(TextEdit::singleline(&mut options.null).desired_width(80.0));
+                        ui.label(self.trans.encoding());
+                        egui::ComboBox::from_id_salt("import_encoding")
+                            .selected_text(&options.encoding)
+                            .show_ui(ui, |ui| {
+                                for encoding in database::copy::ENCODINGS {
+                                    ui.selectable_value(&mut options.encoding, encoding.to_string(), *encoding);
+                                }
+                            });
+                        ui.end_row();
+
+                        ui.checkbox(&mut options.header, self.trans.header_row());
+                        ui.end_row();
+                    });
+                });
+
+                // A tab is written as \t
+                let csv_byte = |field: &str| match field {
+                    "\\t" => Some(b'\t'),
+                    field if field.len() == 1 => field.bytes().next(),
+                    _ => None,
+                };
+                if let Some(delimiter) = csv_byte(&import_window.delimiter_field) {
+                    options.delimiter = delimiter;
+                }
+                if let Some(quote) = csv_byte(&import_window.quote_field) {
+                    options.quote = quote;
+                }
+
+                if options != import_window.options {
+                    import_window.options = options;
+                    import_window.preview = None;
+                }
+
+                ui.separator();
+
+                match &import_window.preview {
+                    Some(Ok(preview)) => {
+                        ui.label(RichText::new(self.trans.preview()).strong());
+
+                        ScrollArea::both().id_salt("import_preview").max_height(180.0).show(ui, |ui| {
+                            Grid::new("import_preview_grid").striped(true).show(ui, |ui| {
+                                for column in &preview.columns {
+                                    ui.label(RichText::new(column).strong());
+                                }
+                                ui.end_row();
+
+                                for row in &preview.rows {
+                                    for value in row {
+                                        ui.label(value);
+                                    }
+                                    ui.end_row();
+                                }
+                            });
+                        });
+
+                        ui.separator();
+                        ui.label(RichText::new(self.trans.column_mapping()).strong());
+
+                        match &table_columns {
+                            Some(Ok(table_columns)) => {
+                                ScrollArea::vertical().id_salt("import_mapping").max_height(180.0).show(ui, |ui| {
+                                    ui.add_enabled_ui(!running, |ui| {
+                                        Grid::new("import_mapping_grid").num_columns(2).show(ui, |ui| {
+                                            for (index, (name, target)) in preview.columns.iter().zip(import_window.mapping.iter_mut()).enumerate() {
+                                                ui.label(name);
+                                                egui::ComboBox::from_id_salt(("import_mapping", index))
+                                                    .selected_text(target.clone().unwrap_or_else(|| self.trans.skip_column()))
+                                                    .show_ui(ui, |ui| {
+                                                        ui.selectable_value(target, None, self.trans.skip_column());
+                                                        for column in table_columns {
+                                                            ui.selectable_value(target, Some(column.clone()), column);
+                                                        }
+                                                    });
+                                                ui.end_row();
+                                            }
+                                        });
+                                    });
+                                });
+                            },
+                            Some(Err(e)) => {
+                                ui.colored_label(ui.visuals().error_fg_color, e);
+                            },
+                            None => {
+                                ui.spinner();
+                            },
+                        }
+                    },
+                    Some(Err(e)) => {
+                        ui.colored_label(ui.visuals().error_fg_color, e);
+                    },
+                    None => {},
+                }
+
+                if let Some(progress) = &import_window.progress {
+                    ui.separator();
+
+                    match progress.result() {
+                        Some(Ok(rows)) => {
+                            ui.label(self.trans.imported_rows(rows));
+                        },
+                        Some(Err(_)) if progress.is_cancelled() => {
+                            ui.label(self.trans.cancelled());
+                        },
+                        Some(Err(e)) => {
+                            ui.colored_label(ui.visuals().error_fg_color, e);
+                        },
+                        None => {
+                            let fraction = match progress.total_bytes() {
+                                Some(total_bytes) if total_bytes > 0 => progress.bytes() as f32 / total_bytes as f32,
+                                _ => 0.0,
+                            };
+
+                            ui.add(egui::ProgressBar::new(fraction).text(self.trans.rows(progress.rows())));
+                        },
+                    }
+                }
+
+                ui.with_layout(Layout::top_down(Align::RIGHT), |ui| {
+                    ui.separator();
+
+                    ui.horizontal(|ui| {
+                        let columns: Vec<(usize, String)> = import_window.mapping
+                            .iter()
+                            .enumerate()
+                            .filter_map(|(index, target)| Some((index, target.clone()?)))
+                            .collect();
+                        let ready = matches!(import_window.preview, Some(Ok(_))) && !columns.is_empty();
+
+                        if running {
+                            if ui.button(self.trans.cancel()).clicked() {
+                                if let Some(progress) = &import_window.progress {
+                                    progress.cancel();
+                                }
+                            }
+                        } else if ui.add_enabled(ready, Button::new(self.trans.import())).clicked() {
+                            if let (Some(database), Some(file_path)) = (&import_window.database, &import_window.file_path) {
+                                import_window.progress = Some(database.import_csv(
+                                    &self.runtime,
+                                    import_window.table.clone(),
+                                    columns,
+                                    file_path.clone(),
+                                    import_window.options.clone(),
+                                ));
+                            }
+                        }
+                        if ui.add_enabled(!running, Button::new(self.trans.close())).clicked() {
+                            *import_window = structs::ImportWindow::default();
+                        }
+                    });
+                });
+            });
+        }
+
+        if let Some(structs::SelectFileDialogAction::ExportCopy) = self.select_file_dialog_action {
+            let picked = self.select_file_dialog.take_picked();
+
+            if picked.is_some() || matches!(self.select_file_dialog.state(), DialogState::Cancelled | DialogState::Closed) {
+                self.select_file_dialog_action = None;
+
+                self.export_window.show = true;
+            }
+
+            if let (Some(file_path), Some(database), Some(source)) = (picked, &self.export_window.database, &self.export_window.source) {
+                self.export_window.progress = Some(database.export(
+                    &self.runtime,
+                    source.clone(),
+                    file_path,
+                    self.export_window.format,
+                    self.config.settings.csv_null.clone(),
+                ));
+            }
+        }
+
+        if self.export_window.show {
+            let export_window = &mut self.export_window;
+
+            let running = export_window.progress.as_ref().is_some_and(|progress| progress.result().is_none());
+            if running {
+                ctx.request_repaint_after(Duration::from_millis(100));
+            }
+
+            Modal::new(Id::new("export_modal")).show(ctx, |ui| {
+                ui.set_width(480.0);
+
+                match &export_window.source {
+                    Some(database::copy::CopySource::Table(table)) => widgets::modal_label(ui, self.trans.export_table_name(table)),
+                    Some(database::copy::CopySource::Query { statement, .. }) => {
+                        widgets::modal_label(ui, self.trans.export_full_query());
+
+                        ScrollArea::vertical().id_salt("export_query").max_height(120.0).show(ui, |ui| {
+                            ui.label(RichText::new(statement).code());
+                        });
+                    },
+                    None => {},
+                }
+
+                ui.add_space(8.0);
+
+                ui.add_enabled_ui(!running, |ui| {
+                    ui.label(self.trans.format());
+                    for format in database::copy::CopyFormat::ALL {
+                        ui.radio_value(&mut export_window.format, format, format.to_string());
+                    }
+                });
+
+                if let Some(progress) = &export_window.progress {
+                    ui.separator();
+
+                    match progress.result() {
+                        Some(Ok(rows)) => {
+                            ui.label(self.trans.exported_rows(rows));
+                        },
+                        Some(Err(_)) if progress.is_cancelled() => {
+                            ui.label(self.trans.cancelled());
+                        },
+                        Some(Err(e)) => {
+                            ui.colored_label(ui.visuals().error_fg_color, e);
+                        },
+                        None => {
+                            ui.horizontal(|ui| {
+                                ui.spinner();
+                                ui.label(format!(
+                                    "{}, {:.1} MiB",
+                                    self.trans.rows(progress.rows()),
+                                    progress.bytes() as f64 / (1024.0 * 1024.0),
+                                ));
+                            });
+                        },
+                    }
+                }
+
+                ui.with_layout(Layout::top_down(Align::RIGHT), |ui| {
+                    ui.separator();
+
+                    ui.horizontal(|ui| {
+                        if running {
+                            if ui.button(self.trans.cancel()).clicked() {
+                                if let Some(progress) = &export_window.progress {
+                                    progress.cancel();
+                                }
+                            }
+                        } else if ui.button(self.trans.export()).clicked() {
+                            export_window.show = false;
+                            export_window.progress = None;
+
+                            let name = match &export_window.source {
+                                Some(database::copy::CopySource::Table(table)) => table.name.as_str(),
+                                _ => "query",
+                            };
+                            self.select_file_dialog_action = Some(structs::SelectFileDialogAction::ExportCopy);
+                            Self::save_file_as(&mut self.select_file_dialog, &format!("{}.{}", name, export_window.format.extension()));
+                        }
+                        if ui.add_enabled(!running, Button::new(self.trans.close())).clicked() {
+                            *export_window = structs::ExportWindow::default();
+                        }
+                    });
+                });
+            });
+        }
+
+        if self.result_export_window.show {
+            Modal::new(Id::new("result_export_modal")).show(ctx, |ui| {
+                ui.set_width(360.0);
+
+                widgets::modal_label(ui, self.trans.export_results());
+
+                let options = &mut self.result_export_window.options;
+
+                ui.label(self.trans.format());
+                for format in export::ResultFormat::ALL {
+                    ui.radio_value(&mut options.format, format, format.to_string());
+                }
+
+                if options.format == export::ResultFormat::Insert {
+                    ui.add_space(8.0);
+
+                    Grid::new("result_export_options").num_columns(2).show(ui, |ui| {
+                        ui.label(self.trans.target_table());
+                        ui.add(TextEdit::singleline(&mut options.table_name).desired_width(200.0));
+                        ui.end_row();
+
+                        ui.label(self.trans.rows_per_insert());
+                        ui.add(DragValue::new(&mut options.batch_size).range(1..=10000));
+                        ui.end_row();
+                    });
+                }
+
+                ui.with_layout(Layout::top_down(Align::RIGHT), |ui| {
+                    ui.separator();
+
+                    ui.horizontal(|ui| {
+                        let ready = options.format != export::ResultFormat::Insert || !options.table_name.trim().is_empty();
+
+                        if ui.add_enabled(ready, Button::new(self.trans.export())).clicked() {
+                            self.result_export_window.show = false;
+
+                            self.select_file_dialog_action = Some(structs::SelectFileDialogAction::ExportResult);
+                            Self::save_file_as(&mut self.select_file_dialog, &format!("result.{}", options.format.extension()));
+                        }
+                        if ui.button(self.trans.back()).clicked() {
+                            self.result_export_window.show = false;
+                        }
+                    });
+                });
+            });
+        }
+
+        if self.close_page_window.show {
+            if let Some(page) = self.close_page_window.page_index.and_then(|idx| self.pages.pages.get(idx)) {
+                let title = page.title.clone();
+
+                Modal::new(Id::new("close_page_modal")).show(ctx, |ui| {
+                    widgets::modal_label(ui, self.trans.close_page());
+
+                    ui.label(self.trans.uncommitted_transaction_confirmation(&title));
+
+                    ui.with_layout(Layout::top_down(Align::RIGHT), |ui| {
+                        ui.separator();
+
+                        ui.horizontal(|ui| {
+                            if ui.button(self.trans.yes()).clicked() {
+                                if let Some(idx) = self.close_page_window.page_index {
+                                    self.actions.push(structs::Action::ClosePage(idx));
+                                }
+                                self.close_page_window = structs::ClosePageWindow::default();
+                            }
+                            if ui.button(self.trans.no()).clicked() {
+                                self.close_page_window = structs::ClosePageWindow::default();
+                            }
+                        });
+                    });
+                });
+            } else {
+                self.close_page_window = structs::ClosePageWindow::default();
+            }
+        }
+
         if self.delete_server_window.show {
             if let Some(server) = &self.delete_server_window.server {
                 let needed_id_string = format!("server:{}:{}:{}", server.ip, server.port, server.user);
@@ -579,7 +1291,7 @@ impl Main<'_> {
                             ui.end_row();
 
                             ui.label(self.trans.server_address());
-                            ui.add(TextEdit::singleline(&mut self.edit_server_window.ip_field).background_color(input_color));
+                            ui.add(TextEdit::singleline(&mut self.edit_server_window.ip_field).hint_text(self.trans.server_address_hint()).background_color(input_color));
                             ui.end_row();
 
                             ui.label(self.trans.port());
@@ -591,16 +1303,56 @@ impl Main<'_> {
                             ui.add(field.background_color(input_color));
                             ui.end_row();
 
-                            ui.label(self.trans.user());
-                            ui.add(TextEdit::singleline(&mut self.edit_server_window.user_field).background_color(input_color));
+                            ui.label(self.trans.user());
+                            ui.add(TextEdit::singleline(&mut self.edit_server_window.user_field).background_color(input_color));
+                            ui.end_row();
+
+                            ui.label(self.trans.password());
+                            ui.add(TextEdit::singleline(&mut self.edit_server_window.password_field).background_color(input_color));
+                            ui.end_row();
+
+                            ui.label(self.trans.service_database());
+                            ui.add(TextEdit::singleline(&mut self.edit_server_window.service_database_field).background_color(input_color));
+                            ui.end_row();
+
+                            ui.label(self.trans.statement_timeout());
+                            let is_error = self.edit_server_window.statement_timeout_field.parse::<u32>().is_err();
+                            let mut field = TextEdit::singleline(&mut self.edit_server_window.statement_timeout_field);
+                            if is_error {
+                                field = field.text_color(Color32::from_rgb(255, 0, 0));
+                            }
+                            ui.add(field.background_color(input_color));
+                            ui.end_row();
+
+                            ui.label(self.trans.lock_timeout());
+                            let is_error = self.edit_server_window.lock_timeout_field.parse::<u32>().is_err();
+                            let mut field = TextEdit::singleline(&mut self.edit_server_window.lock_timeout_field);
+                            if is_error {
+                                field = field.text_color(Color32::from_rgb(255, 0, 0));
+                            }
+                            ui.add(field.background_color(input_color));
+                            ui.end_row();
+
+                            ui.label(self.trans.ssl_mode());
+                            egui::ComboBox::from_id_salt("edit_server_ssl_mode")
+                                .selected_text(self.edit_server_window.ssl_mode.to_string())
+                                .show_ui(ui, |ui| {
+                                    for ssl_mode in structs::SslMode::ALL {
+                                        ui.selectable_value(&mut self.edit_server_window.ssl_mode, ssl_mode, ssl_mode.to_string());
+                                    }
+                                });
                             ui.end_row();
 
-                            ui.label(self.trans.password());
-                            ui.add(TextEdit::singleline(&mut self.edit_server_window.password_field).background_color(input_color));
+                            ui.label(self.trans.ssl_root_cert());
+                            ui.add(TextEdit::singleline(&mut self.edit_server_window.ssl_root_cert_field).hint_text(self.trans.ssl_root_cert_hint()).background_color(input_color));
                             ui.end_row();
 
-                            ui.label(self.trans.service_database());
-                            ui.add(TextEdit::singleline(&mut self.edit_server_window.service_database_field).background_color(input_color));
+                            ui.label(self.trans.ssl_client_cert());
+                            ui.add(TextEdit::singleline(&mut self.edit_server_window.ssl_client_cert_field).background_color(input_color));
+                            ui.end_row();
+
+                            ui.label(self.trans.ssl_client_key());
+                            ui.add(TextEdit::singleline(&mut self.edit_server_window.ssl_client_key_field).background_color(input_color));
                             ui.end_row();
                         });
 
@@ -647,7 +1399,7 @@ impl Main<'_> {
                     };
 
                     let is_service_database_error = {
-                        if self.add_server_window.service_database_field.is_empty() {
+                        if self.edit_server_window.service_database_field.is_empty() {
                             ui.label(self.trans.service_database_is_required());
                             true
                         } else {
@@ -655,7 +1407,24 @@ impl Main<'_> {
                         }
                     };
 
-                    let enable_save_button = !is_name_error && !is_ip_error && !is_port_error && !is_user_error && !is_service_database_error;
+                    let is_timeout_error = {
+                        if self.edit_server_window.statement_timeout_field.parse::<u32>().is_err() || self.edit_server_window.lock_timeout_field.parse::<u32>().is_err() {
+                            ui.label(self.trans.incorrect_timeout_value());
+                            true
+                        } else {
+                            false
+                        }
+                    };
+                    let is_ssl_error = {
+                        if self.edit_server_window.ssl_client_cert_field.trim().is_empty() != self.edit_server_window.ssl_client_key_field.trim().is_empty() {
+                            ui.label(self.trans.client_cert_and_key_are_required_together());
+                            true
+                        } else {
+                            false
+                        }
+                    };
+
+                    let enable_save_button = !is_name_error && !is_ip_error && !is_port_error && !is_user_error && !is_service_database_error && !is_timeout_error && !is_ssl_error;
 
                     ui.with_layout(Layout::top_down(Align::RIGHT), |ui| {
                         ui.separator();
@@ -669,6 +1438,15 @@ impl Main<'_> {
                                     user: self.edit_server_window.user_field.clone(),
                                     password: self.edit_server_window.password_field.clone(),
                                     service_database: self.edit_server_window.service_database_field.clone(),
+                                    statement_timeout: self.edit_server_window.statement_timeout_field.parse::<u32>().unwrap(),
+                                    lock_timeout: self.edit_server_window.lock_timeout_field.parse::<u32>().unwrap(),
+                                    ssl_mode: self.edit_server_window.ssl_mode,
+                                    ssl_root_cert: Self::optional_field(&self.edit_server_window.ssl_root_cert_field),
+                                    ssl_client_cert: Self::optional_field(&self.edit_server_window.ssl_client_cert_field),
+                                    ssl_client_key: Self::optional_field(&self.edit_server_window.ssl_client_key_field),
+                                    // Not editable in the form, kept from the connection string the server was added with
+                                    application_name: self.edit_server_window.original_server.as_ref().and_then(|server| server.application_name.clone()),
+                                    options: self.edit_server_window.original_server.as_ref().and_then(|server| server.options.clone()),
                                 };
                                 let mut original_server_index: Option<usize> = None;
 
@@ -741,6 +1519,15 @@ impl Main<'_> {
             if self.settings_window.language.is_none() {
                 self.settings_window.language = Some(self.config.settings.language.clone());
             }
+            if self.settings_window.display_time_zone.is_none() {
+                self.settings_window.display_time_zone = Some(self.config.settings.display_time_zone);
+            }
+            if self.settings_window.csv_null.is_none() {
+                self.settings_window.csv_null = Some(self.config.settings.csv_null.clone());
+            }
+            if self.settings_window.max_rows.is_none() {
+                self.settings_window.max_rows = Some(self.config.settings.max_rows);
+            }
 
             Modal::new(Id::new("settings_modal")).show(ctx, |ui| {
                 widgets::modal_label(ui, self.trans.settings());
@@ -790,6 +1577,42 @@ impl Main<'_> {
                             });
                             ui.end_row();
 
+                            ui.with_layout(Layout::top_down(Align::LEFT), |ui| {
+                                ui.label(self.trans.time_zone());
+                            });
+                            let time_zone_name = match self.settings_window.display_time_zone {
+                                Some(structs::DisplayTimeZone::Utc) => self.trans.utc(),
+                                Some(structs::DisplayTimeZone::Local) => self.trans.local_time_zone(),
+                                Some(structs::DisplayTimeZone::Session) => self.trans.session_time_zone(),
+                                None => "".to_string(),
+                            };
+                            CollapsingHeader::new(time_zone_name).show(ui, |ui| {
+                                if ui.button(self.trans.utc()).clicked() {
+                                    self.settings_window.display_time_zone = Some(structs::DisplayTimeZone::Utc);
+                                }
+                                if ui.button(self.trans.local_time_zone()).clicked() {
+                                    self.settings_window.display_time_zone = Some(structs::DisplayTimeZone::Local);
+                                }
+                                if ui.button(self.trans.session_time_zone()).clicked() {
+                                    self.settings_window.display_time_zone = Some(structs::DisplayTimeZone::Session);
+                                }
+                            });
+                            ui.end_row();
+
+                            ui.label(self.trans.csv_null());
+                            if let Some(csv_null) = &mut self.settings_window.csv_null {
+                                ui.add(TextEdit::singleline(csv_null)
+                                    .hint_text(self.trans.empty_field())
+                                    .background_color(self.config.settings.theme.text_input_color()));
+                            }
+                            ui.end_row();
+
+                            ui.label(self.trans.max_rows());
+                            if let Some(max_rows) = &mut self.settings_window.max_rows {
+                                ui.add(DragValue::new(max_rows).range(1..=u32::MAX).speed(100));
+                            }
+                            ui.end_row();
+
                             ui.label(self.trans.change_password());
                             if ui.button(self.trans.change_password()).clicked() {
                                 self.change_password_window.show = true;
@@ -805,6 +1628,9 @@ impl Main<'_> {
                             self.config.settings.scale_factor = self.settings_window.scale_factor;
                             self.config.settings.theme = self.settings_window.theme.clone();
                             self.config.settings.language = self.settings_window.language.clone().unwrap();
+                            self.config.settings.display_time_zone = self.settings_window.display_time_zone.unwrap();
+                            self.config.settings.csv_null = self.settings_window.csv_null.clone().unwrap();
+                            self.config.settings.max_rows = self.settings_window.max_rows.unwrap();
 
                             self.settings_window = structs::SettingsWindow::default();
 
@@ -916,7 +1742,18 @@ impl Main<'_> {
                     btn.context_menu(|ui| {
                         if ui.button(self.trans.close()).clicked() {
                             ui.memory_mut(|mem| mem.close_popup());
-                            self.actions.push(structs::Action::ClosePage(idx));
+
+                            let has_open_transaction = match &page.page_type {
+                                structs::PageType::SQLQuery(sqlquery_page) => sqlquery_page.session.has_open_transaction(),
+                                _ => false,
+                            };
+
+                            if has_open_transaction {
+                                self.close_page_window.show = true;
+                                self.close_page_window.page_index = Some(idx);
+                            } else {
+                                self.actions.push(structs::Action::ClosePage(idx));
+                            }
                         }
                     });
                 }
@@ -936,7 +1773,8 @@ impl Main<'_> {
                         return;
                     }
 
-                    let page = &mut self.pages.pages[self.pages.current_page_index as usize];
+                    let page_index = self.pages.current_page_index as usize;
+                    let page = &mut self.pages.pages[page_index];
 
                     match &mut page.page_type {
                         structs::PageType::Welcome => {
@@ -992,21 +1830,238 @@ impl Main<'_> {
                             ui.vertical(|ui| {
                                 ui.horizontal(|ui| {
                                     let code_is_empty = sqlquery_page.code.is_empty();
+                                    let is_running = sqlquery_page.statements.iter().any(|statement| {
+                                        match &*statement.sql_query_execution_status.lock().unwrap() {
+                                            structs::SQLQueryExecutionStatusType::Pending | structs::SQLQueryExecutionStatusType::Running => true,
+                                            structs::SQLQueryExecutionStatusType::Success(result) => result.fetching,
+                                            _ => false,
+                                        }
+                                    });
+                                    // Statements run one after another in the session, so nothing new starts until they are done
+                                    let can_run = !code_is_empty && !is_running;
+
+                                    if ui.add_enabled(can_run, Button::new(self.trans.run_f5())).clicked() || (ui.input(|i| i.key_pressed(Key::F5) && !i.modifiers.shift && can_run)) {
+                                        let range = sqlquery_page.code_selection.as_ref()
+                                            .map(|selection| Self::selection_byte_range(&sqlquery_page.code, selection))
+                                            .filter(|range| !sqlquery_page.code[range.clone()].trim().is_empty())
+                                            .unwrap_or(0..sqlquery_page.code.len());
+
+                                        Self::run_sql_query(&self.runtime, &self.config.settings, &mut self.parameters_window, page_index, sqlquery_page, range, None);
+                                    }
+
+                                    // Consumed before the editor is drawn so it does not reach the text
+                                    let run_statement_pressed = ui.input_mut(|i| i.consume_shortcut(&KeyboardShortcut::new(Modifiers::COMMAND, Key::Enter)));
+                                    if ui.add_enabled(can_run, Button::new(self.trans.run_statement_ctrl_enter())).clicked() || (run_statement_pressed && can_run) {
+                                        let cursor = sqlquery_page.code_selection.as_ref()
+                                            .map(|selection| Self::selection_byte_range(&sqlquery_page.code, selection).start)
+                                            .unwrap_or(0);
+
+                                        if let Some(range) = database::script::statement_at(&sqlquery_page.code, cursor) {
+                                            Self::run_sql_query(&self.runtime, &self.config.settings, &mut self.parameters_window, page_index, sqlquery_page, range, None);
+                                        }
+                                    }
+
+                                    let mut explain = None;
+                                    if ui.add_enabled(can_run, Button::new(self.trans.explain())).clicked() {
+                                        explain = Some(database::explain::ExplainMode::Plan);
+                                    }
+                                    if ui.add_enabled(can_run, Button::new(self.trans.explain_analyze()))
+                                        .on_hover_text(self.trans.explain_analyze_hint())
+                                        .clicked() {
+
+                                        explain = Some(database::explain::ExplainMode::Analyze);
+                                    }
+                                    if explain.is_some() {
+                                        // The selection is explained if there is one, the statement at the cursor otherwise
+                                        let selection = sqlquery_page.code_selection.as_ref()
+                                            .map(|selection| Self::selection_byte_range(&sqlquery_page.code, selection));
+                                        let range = selection.clone()
+                                            .filter(|range| !sqlquery_page.code[range.clone()].trim().is_empty())
+                                            .or_else(|| database::script::statement_at(&sqlquery_page.code, selection.map_or(0, |range| range.start)));
+
+                                        if let Some(range) = range {
+                                            Self::run_sql_query(&self.runtime, &self.config.settings, &mut self.parameters_window, page_index, sqlquery_page, range, explain);
+                                        }
+                                    }
 
-                                    if ui.add_enabled(!code_is_empty, Button::new(self.trans.run_f5())).clicked() || (ui.input(|i| i.key_pressed(Key::F5) && !code_is_empty)) {
-                                        let runtime = &self.runtime;
+                                    // The export runs in the session, which a running statement holds
+                                    if ui.add_enabled(can_run, Button::new(self.trans.export_full_query())).clicked() {
+                                        // Like explain, the selection or the statement at the cursor
+                                        let selection = sqlquery_page.code_selection.as_ref()
+                                            .map(|selection| Self::selection_byte_range(&sqlquery_page.code, selection));
+                                        let range = selection.clone()
+                                            .filter(|range| !sqlquery_page.code[range.clone()].trim().is_empty())
+                                            .or_else(|| database::script::statement_at(&sqlquery_page.code, selection.map_or(0, |range| range.start)));
+
+                                        // The first statement of a selection, without the semicolon and comments after it
+                                        let statement = range.and_then(|range| {
+                                            let code = &sqlquery_page.code[range];
+                                            database::script::split_statements(code).first().map(|statement| code[statement.clone()].to_string())
+                                        });
+
+                                        if let Some(statement) = statement {
+                                            let parameters = database::script::statement_parameters([statement.as_str()]);
+
+                                            if parameters.is_empty() {
+                                                Self::export_statement(&mut self.export_window, sqlquery_page, statement);
+                                            } else {
+                                                self.parameters_window = structs::ParametersWindow {
+                                                    show: true,
+                                                    page_index: Some(page_index),
+                                                    statements: vec![(statement, None)],
+                                                    executed_range: None,
+                                                    values: Self::parameter_fields(sqlquery_page, parameters),
+                                                    export: true,
+                                                };
+                                            }
+                                        }
+                                    }
+
+                                    // Nothing can be cancelled before the session knows its backend
+                                    let backend_pid = sqlquery_page.session.pid();
+                                    let cancel_button = ui.add_enabled(is_running && backend_pid.is_some(), Button::new(self.trans.cancel_shift_f5()));
+                                    let cancel_pressed = cancel_button.clicked() || (ui.input(|i| i.modifiers.shift && i.key_pressed(Key::F5)) && is_running);
+                                    if is_running && backend_pid.is_none() {
+                                        cancel_button.on_disabled_hover_text(self.trans.cancel_not_connected());
+                                    } else if let (true, Some(pid)) = (cancel_pressed, backend_pid) {
+                                        let database_clone = sqlquery_page.database.clone();
+                                        let statements = sqlquery_page.statements.clone();
+                                        for statement in &statements {
+                                            statement.cancel_requested.store(true, Ordering::Relaxed);
+                                        }
+
+                                        self.runtime.spawn(async move {
+                                            // Statements are only marked once the server took the request
+                                            match database_clone.cancel_query(pid).await {
+                                                Ok(true) => {
+                                                    for status in statements.iter().map(|statement| &statement.sql_query_execution_status) {
+                                                        let mut sql_query_execution_status = status.lock().unwrap();
+                                                        match &*sql_query_execution_status {
+                                                            structs::SQLQueryExecutionStatusType::Pending => {
+                                                                *sql_query_execution_status = structs::SQLQueryExecutionStatusType::Skipped;
+                                                            },
+                                                            structs::SQLQueryExecutionStatusType::Running => {
+                                                                *sql_query_execution_status = structs::SQLQueryExecutionStatusType::Cancelled;
+                                                            },
+                                                            structs::SQLQueryExecutionStatusType::Success(result) if result.fetching => {
+                                                                *sql_query_execution_status = structs::SQLQueryExecutionStatusType::Cancelled;
+                                                            },
+                                                            _ => {},
+                                                        }
+                                                    }
+                                                },
+                                                result => {
+                                                    for statement in &statements {
+                                                        statement.cancel_requested.store(false, Ordering::Relaxed);
+                                                    }
+
+                                                    match result {
+                                                        Ok(_) => error!("Backend {} could not be signalled to cancel its query", pid),
+                                                        Err(e) => error!("Error cancelling query on backend {}: {}", pid, e),
+                                                    }
+                                                },
+                                            }
+                                        });
+                                    }
+
+                                    ui.separator();
+
+                                    let transaction_status = sqlquery_page.session.transaction_status();
+                                    let in_transaction = transaction_status != database::TransactionStatus::Idle;
+
+                                    let mut transaction_command = None;
+                                    if ui.add_enabled(!is_running && !in_transaction, Button::new(self.trans.begin())).clicked() {
+                                        transaction_command = Some("BEGIN");
+                                    }
+                                    if ui.add_enabled(!is_running && in_transaction, Button::new(self.trans.commit())).clicked() {
+                                        transaction_command = Some("COMMIT");
+                                    }
+                                    if ui.add_enabled(!is_running && in_transaction, Button::new(self.trans.rollback())).clicked() {
+                                        transaction_command = Some("ROLLBACK");
+                                    }
+                                    if let Some(command) = transaction_command {
+                                        Self::run_statements(&self.runtime, &self.config.settings, sqlquery_page, vec![(command.to_string(), None)]);
+                                    }
 
-                                        sqlquery_page.sql_query_execution_status = Some(Arc::new(Mutex::new(structs::SQLQueryExecutionStatusType::Running)));
+                                    match transaction_status {
+                                        database::TransactionStatus::Idle => match sqlquery_page.session.cursor_expires_at() {
+                                            Some(expires_at) => {
+                                                let seconds_left = expires_at.saturating_duration_since(Instant::now()).as_secs_f32().ceil() as u64;
 
+                                                ui.label(RichText::new(self.trans.cursor_transaction_open(seconds_left)).color(Color32::from_rgb(200, 140, 0)))
+                                                    .on_hover_text(self.trans.cursor_transaction_hint(database::CURSOR_TRANSACTION_TIMEOUT.as_secs()));
+                                                ctx.request_repaint_after(Duration::from_secs(1));
+                                            },
+                                            None => {
+                                                ui.label(RichText::new(self.trans.transaction_idle()).weak());
+                                            },
+                                        },
+                                        database::TransactionStatus::Open => {
+                                            ui.label(RichText::new(self.trans.transaction_open()).color(Color32::from_rgb(0, 160, 0)));
+                                        },
+                                        database::TransactionStatus::Aborted => {
+                                            ui.label(RichText::new(self.trans.transaction_aborted()).color(Color32::RED))
+                                                .on_hover_text(self.trans.transaction_aborted_hint());
+                                        },
+                                    }
+
+                                    ui.separator();
+
+                                    match (sqlquery_page.session.pid(), sqlquery_page.session.error()) {
+                                        (Some(pid), _) => {
+                                            ui.label(RichText::new(self.trans.backend_pid(pid)).weak());
+                                        },
+                                        (None, Some(e)) if sqlquery_page.session.lost_transaction() => {
+                                            ui.label(RichText::new(self.trans.transaction_rolled_back()).color(Color32::RED)).on_hover_text(e);
+                                        },
+                                        (None, Some(e)) => {
+                                            ui.label(RichText::new(self.trans.disconnected()).color(Color32::RED)).on_hover_text(e);
+                                        },
+                                        (None, None) => {
+                                            ui.label(RichText::new(self.trans.not_connected()).weak());
+                                        },
+                                    }
+
+                                    // Reconnecting would silently roll back the transaction
+                                    let reconnect_button = ui.add_enabled(!is_running && !in_transaction, Button::new(self.trans.reconnect()));
+                                    if in_transaction {
+                                        reconnect_button.on_disabled_hover_text(self.trans.reconnect_in_transaction());
+                                    } else if reconnect_button.clicked() {
                                         let database_clone = sqlquery_page.database.clone();
-                                        let code_clone = sqlquery_page.code.clone();
-                                        let sql_query_execution_status = sqlquery_page.sql_query_execution_status.clone();
+                                        let session_clone = sqlquery_page.session.clone();
 
-                                        runtime.spawn(async move {
-                                            Self::fetch_sql_query(database_clone, &code_clone, sql_query_execution_status).await;
+                                        self.runtime.spawn(async move {
+                                            if let Err(e) = database_clone.reconnect_session(&session_clone).await {
+                                                error!("Error reconnecting session: {}", e);
+                                            }
                                         });
                                     }
 
+                                    ui.separator();
+
+                                    ui.menu_button(self.trans.timeouts(), |ui| {
+                                        let mut override_timeouts = sqlquery_page.timeouts.is_some();
+                                        if ui.checkbox(&mut override_timeouts, self.trans.override_server_timeouts()).changed() {
+                                            sqlquery_page.timeouts = override_timeouts.then(|| sqlquery_page.database.timeouts());
+                                        }
+
+                                        let mut timeouts = sqlquery_page.timeouts.unwrap_or(sqlquery_page.database.timeouts());
+                                        ui.add_enabled_ui(override_timeouts, |ui| {
+                                            Grid::new("page_timeouts").num_columns(2).show(ui, |ui| {
+                                                ui.label(self.trans.statement_timeout());
+                                                ui.add(DragValue::new(&mut timeouts.statement_timeout));
+                                                ui.end_row();
+
+                                                ui.label(self.trans.lock_timeout());
+                                                ui.add(DragValue::new(&mut timeouts.lock_timeout));
+                                                ui.end_row();
+                                            });
+                                        });
+                                        if override_timeouts {
+                                            sqlquery_page.timeouts = Some(timeouts);
+                                        }
+                                    });
+
                                     if ui.add_enabled(!code_is_empty, Button::new(self.trans.save())).clicked() || (ui.input(|i| i.modifiers.ctrl && i.key_pressed(Key::S)) && !code_is_empty) {
                                         if sqlquery_page.code_file_path.is_some() {
                                             Self::save_code(sqlquery_page);
@@ -1020,13 +2075,10 @@ impl Main<'_> {
                                         self.select_file_dialog.pick_file();
                                     }
 
-                                    if ui.add_enabled(!sqlquery_page.output_is_empty, Button::new(self.trans.export_to_csv())).clicked() || (ui.input(|i| i.modifiers.ctrl && i.key_pressed(Key::E)) && !sqlquery_page.output_is_empty) {
-                                        self.select_file_dialog_action = Some(structs::SelectFileDialogAction::ExportToCsv);
-                                        self.select_file_dialog.save_file();
+                                    if ui.add_enabled(!sqlquery_page.output_is_empty, Button::new(self.trans.export_results())).clicked() || (ui.input(|i| i.modifiers.ctrl && i.key_pressed(Key::E)) && !sqlquery_page.output_is_empty) {
+                                        self.result_export_window.show = true;
                                     }
 
-                                    self.select_file_dialog.update(ctx);
-
                                     if let Some(action) = &self.select_file_dialog_action {
                                         match action {
                                             structs::SelectFileDialogAction::SaveFile => {
@@ -1055,18 +2107,26 @@ impl Main<'_> {
                                                     }
                                                 }
                                             },
-                                            structs::SelectFileDialogAction::ExportToCsv => {
-                                                if let Some(file_path) = self.select_file_dialog.take_picked() {
-                                                    match sqlquery_page.sql_query_execution_status.clone().unwrap().lock().unwrap().clone() {
-                                                        structs::SQLQueryExecutionStatusType::Success(sqlquery_execution_success) => {
-                                                            let result = &sqlquery_execution_success.result;
+                                            structs::SelectFileDialogAction::ExportResult => {
+                                                if let (Some(file_path), Some(statement)) = (self.select_file_dialog.take_picked(), sqlquery_page.statements.get(sqlquery_page.selected_statement)) {
+                                                    self.select_file_dialog_action = None;
 
-                                                            Self::export_output_to_csv(result, file_path.to_string_lossy().to_string());
-                                                        },
-                                                        _ => {},
-                                                    };
+                                                    if let structs::SQLQueryExecutionStatusType::Success(sqlquery_execution_success) = &*statement.sql_query_execution_status.lock().unwrap() {
+                                                        let options = export::ExportOptions {
+                                                            csv_null: self.config.settings.csv_null.clone(),
+                                                            ..self.result_export_window.options.clone()
+                                                        };
+                                                        let result = File::create(&file_path)
+                                                            .map_err(|e| e.to_string())
+                                                            .and_then(|file| export::write_result(&sqlquery_execution_success.result, &options, std::io::BufWriter::new(file)));
+
+                                                        if let Err(e) = result {
+                                                            error!("Error exporting results to {}: {}", file_path.display(), e);
+                                                        }
+                                                    }
                                                 }
-                                            }
+                                            },
+                                            structs::SelectFileDialogAction::ImportCsv | structs::SelectFileDialogAction::ExportCopy => {},
                                         }
                                     }
                                 });
@@ -1082,8 +2142,56 @@ impl Main<'_> {
 
                                 ui.add_space(8.0);
 
+                                let code_editor_id = Id::new("code_editor").with(page_index);
+
+                                // Errors arrive between frames, the cursor is moved to each of them once
+                                for statement in &mut sqlquery_page.statements {
+                                    if statement.error_located {
+                                        continue;
+                                    }
+                                    let position = match &*statement.sql_query_execution_status.lock().unwrap() {
+                                        structs::SQLQueryExecutionStatusType::Error(database::QueryError::Database(e)) => e.position,
+                                        structs::SQLQueryExecutionStatusType::Error(_) => None,
+                                        _ => continue,
+                                    };
+                                    statement.error_located = true;
+
+                                    let Some(offset) = position.and_then(|position| Self::error_offset(&sqlquery_page.code, statement, position)) else {
+                                        continue;
+                                    };
+                                    let char_index = sqlquery_page.code[..offset].chars().count();
+
+                                    let mut state = egui::text_edit::TextEditState::load(ctx, code_editor_id).unwrap_or_default();
+                                    state.cursor.set_char_range(Some(CCursorRange::one(CCursor::new(char_index))));
+                                    state.store(ctx, code_editor_id);
+                                    ctx.memory_mut(|memory| memory.request_focus(code_editor_id));
+
+                                    sqlquery_page.code_selection = Some(char_index..char_index);
+                                    sqlquery_page.error_position = Some(offset);
+                                }
+
                                 let mut theme = egui_extras::syntax_highlighting::CodeTheme::light(12.0);
 
+                                if sqlquery_page.executed_range.as_ref().is_some_and(|(_, time)| time.elapsed() >= EXECUTED_HIGHLIGHT_DURATION) {
+                                    sqlquery_page.executed_range = None;
+                                }
+                                let executed_range = sqlquery_page.executed_range.as_ref().map(|(range, time)| {
+                                    ctx.request_repaint_after(EXECUTED_HIGHLIGHT_DURATION.saturating_sub(time.elapsed()));
+                                    range.clone()
+                                });
+
+                                // The word the error points at, or the character if it's not in a word
+                                let error_range = sqlquery_page.error_position.map(|position| {
+                                    let rest = &sqlquery_page.code[position..];
+                                    let word_length: usize = rest
+                                        .chars()
+                                        .take_while(|c| c.is_alphanumeric() || *c == '_')
+                                        .map(char::len_utf8)
+                                        .sum();
+
+                                    position..position + word_length.max(rest.chars().next().map_or(0, char::len_utf8))
+                                });
+
                                 let mut layouter = |ui: &egui::Ui, string: &str, wrap_width: f32| {
                                     let mut layout_job = egui_extras::syntax_highlighting::highlight(
                                         ui.ctx(),
@@ -1092,20 +2200,36 @@ impl Main<'_> {
                                         string,
                                         "sql",
                                     );
+                                    if let Some(range) = executed_range.clone().filter(|range| range.end <= string.len()) {
+                                        let color = ui.visuals().selection.bg_fill.gamma_multiply(0.5);
+                                        widgets::format_range(&mut layout_job, range, |format| format.background = color);
+                                    }
+                                    if let Some(range) = error_range.clone().filter(|range| range.end <= string.len()) {
+                                        widgets::format_range(&mut layout_job, range, |format| format.underline = Stroke::new(1.5, Color32::RED));
+                                    }
                                     layout_job.wrap.max_width = wrap_width;
                                     ui.fonts(|f| f.layout_job(layout_job))
                                 };
 
-                                let code_editor = ui.add(
-                                    TextEdit::multiline(&mut sqlquery_page.code)
-                                        .font(egui::TextStyle::Monospace)
-                                        .code_editor()
-                                        .desired_width(f32::INFINITY)
-                                        .desired_rows(10)
-                                        .background_color(self.config.settings.theme.text_input_color())
-                                        .hint_text("SELECT * FROM ...")
-                                        .layouter(&mut layouter),
-                                );
+                                let code_editor_output = TextEdit::multiline(&mut sqlquery_page.code)
+                                    .id(code_editor_id)
+                                    .font(egui::TextStyle::Monospace)
+                                    .code_editor()
+                                    .desired_width(f32::INFINITY)
+                                    .desired_rows(10)
+                                    .background_color(self.config.settings.theme.text_input_color())
+                                    .hint_text("SELECT * FROM ...")
+                                    .layouter(&mut layouter)
+                                    .show(ui);
+                                // The editor only reports its cursor while focused, keep the last one for the toolbar
+                                if let Some(cursor_range) = code_editor_output.cursor_range {
+                                    sqlquery_page.code_selection = Some(cursor_range.as_sorted_char_range());
+                                }
+                                if code_editor_output.response.changed() {
+                                    sqlquery_page.executed_range = None;
+                                    sqlquery_page.error_position = None;
+                                }
+                                let code_editor = code_editor_output.response;
                                 if code_editor.secondary_clicked() {
                                     ui.memory_mut(|mem| mem.open_popup(Id::new("code_editor_popup")));
                                 }
@@ -1120,18 +2244,64 @@ impl Main<'_> {
 
                                 ui.add_space(8.0);
 
-                                if let Some(sql_query_execution_status) = &sqlquery_page.sql_query_execution_status {
-                                    let mut sql_query_execution_status_mutex = sql_query_execution_status.lock().unwrap();
+                                if sqlquery_page.statements.len() > 1 {
+                                    ScrollArea::horizontal().id_salt("statement_tabs").show(ui, |ui| {
+                                        ui.horizontal(|ui| {
+                                            for (index, statement) in sqlquery_page.statements.iter().enumerate() {
+                                                let text = RichText::new(format!("{}: {}", index + 1, statement.code.lines().next().unwrap_or_default()));
+                                                let text = match &*statement.sql_query_execution_status.lock().unwrap() {
+                                                    structs::SQLQueryExecutionStatusType::Error(_) => text.color(Color32::RED),
+                                                    structs::SQLQueryExecutionStatusType::Pending | structs::SQLQueryExecutionStatusType::Skipped => text.weak(),
+                                                    _ => text,
+                                                };
+
+                                                let tab = ui.add(Button::new(text).truncate().selected(sqlquery_page.selected_statement == index))
+                                                    .on_hover_text(RichText::new(&statement.code).monospace());
+                                                if tab.clicked() {
+                                                    sqlquery_page.selected_statement = index;
+                                                    sqlquery_page.grid_selection = None;
+                                                }
+                                            }
+                                        });
+                                    });
+
+                                    ui.separator();
+                                }
+
+                                if let Some(statement) = sqlquery_page.statements.get(sqlquery_page.selected_statement).cloned() {
+                                    let mut sql_query_execution_status_mutex = statement.sql_query_execution_status.lock().unwrap();
                                     if let Some(update_page_index) = sqlquery_page.update_page_index {
                                         if let structs::SQLQueryExecutionStatusType::Success(ref mut result) = *sql_query_execution_status_mutex {
                                             result.page_index = update_page_index;
-                                            result.current_page = Some(Self::get_sql_query_slice(&result.result, update_page_index));
                                         }
                                         sqlquery_page.update_page_index = None;
                                     }
+                                    if let Some(fetch_action) = sqlquery_page.fetch_action.take() {
+                                        let query_cursor = statement.cursor.lock().unwrap().take();
+
+                                        if let (structs::SQLQueryExecutionStatusType::Success(ref mut result), Some(query_cursor)) = (&mut *sql_query_execution_status_mutex, query_cursor) {
+                                            result.fetching = true;
+
+                                            let sqlquery_page_clone = (**sqlquery_page).clone();
+                                            let statement_clone = statement.clone();
+                                            let count = match fetch_action {
+                                                structs::FetchAction::More => Some(self.config.settings.max_rows),
+                                                structs::FetchAction::All => None,
+                                            };
+
+                                            self.runtime.spawn(async move {
+                                                Self::fetch_more_sql_query(sqlquery_page_clone, statement_clone, query_cursor, count).await;
+                                            });
+                                        }
+                                    }
                                     let sql_query_execution_status = &*sql_query_execution_status_mutex;
 
                                     match &sql_query_execution_status {
+                                        structs::SQLQueryExecutionStatusType::Pending => {
+                                            sqlquery_page.output_is_empty = true;
+
+                                            ui.label(self.trans.pending());
+                                        }
                                         structs::SQLQueryExecutionStatusType::Running => {
                                             ui.horizontal(|ui| {
                                                 ui.add(Spinner::new());
@@ -1141,7 +2311,8 @@ impl Main<'_> {
                                             ui.separator();
                                         }
                                         structs::SQLQueryExecutionStatusType::Success(result) => {
-                                            let data = result.current_page.as_ref().unwrap();
+                                            let data = &result.result;
+                                            let rows = data.page(result.page_index, ROWS_PER_PAGE as u32);
                                             let rows_count = result.rows_count;
                                             let execution_time = result.execution_time;
 
@@ -1158,62 +2329,201 @@ impl Main<'_> {
                                                 let available_width = ui.available_width();
 
                                                 ui.horizontal(|ui| {
-                                                    ui.label(self.trans.success());
+                                                    if result.fetching {
+                                                        ui.add(Spinner::new());
+                                                        ui.label(self.trans.fetching());
+                                                    } else {
+                                                        ui.label(self.trans.success());
+                                                    }
                                                     ui.separator();
                                                     ui.label(self.trans.rows(rows_count));
                                                     ui.separator();
                                                     ui.label(self.trans.time(execution_time));
+
+                                                    if let Some(command_tag) = &result.command_tag {
+                                                        ui.separator();
+                                                        ui.label(RichText::new(command_tag).monospace());
+                                                    }
+
+                                                    if let Some(plan) = &result.plan {
+                                                        if let Some(planning_time) = plan.planning_time {
+                                                            ui.separator();
+                                                            ui.label(self.trans.planning_time(planning_time));
+                                                        }
+                                                        if let Some(plan_execution_time) = plan.execution_time {
+                                                            ui.separator();
+                                                            ui.label(self.trans.execution_time(plan_execution_time));
+                                                        }
+                                                        ui.separator();
+                                                        ui.checkbox(&mut sqlquery_page.plan_as_table, self.trans.show_as_table());
+                                                    }
+
+                                                    if result.has_more && !result.fetching {
+                                                        ui.separator();
+                                                        if statement.cursor.lock().unwrap().as_ref().is_some_and(|cursor| cursor.is_expired()) {
+                                                            ui.label(RichText::new(self.trans.cursor_expired()).weak())
+                                                                .on_hover_text(self.trans.cursor_transaction_hint(database::CURSOR_TRANSACTION_TIMEOUT.as_secs()));
+                                                        } else {
+                                                            if ui.button(self.trans.fetch_more(self.config.settings.max_rows)).clicked() {
+                                                                sqlquery_page.fetch_action = Some(structs::FetchAction::More);
+                                                            }
+                                                            if ui.button(self.trans.fetch_all()).clicked() {
+                                                                sqlquery_page.fetch_action = Some(structs::FetchAction::All);
+                                                            }
+                                                        }
+                                                    }
                                                 });
 
                                                 ui.separator();
 
-                                                ScrollArea::horizontal().auto_shrink([false, false]).max_width(available_width).max_height(available_height).show(ui, |ui| {
-                                                    TableBuilder::new(ui)
-                                                        .striped(true)
-                                                        .auto_shrink([false, false])
-                                                        .columns(Column::remainder().resizable(true), data.keys().len())
-                                                        .header(16.0, |mut header| {
-                                                            for column_name in data.keys() {
-                                                                header.col(|ui| {
-                                                                    ui.add(
-                                                                        Label::new(RichText::new(column_name).strong().monospace())
-                                                                            .wrap_mode(egui::TextWrapMode::Extend)
-                                                                    );
-                                                                });
-                                                            }
-                                                        })
-                                                        .body(|mut body| {
-                                                            let values = data.values()
-                                                                .map(|v| v.iter()
-                                                                    .map(|x| x.to_string())
-                                                                    .collect::<Vec<String>>())
-                                                                .collect::<Vec<Vec<String>>>();
-
-                                                            for i in 0..values.iter().next().unwrap().len() {
-                                                                body.row(16.0, |mut row| {
-                                                                    for value in &values {
-                                                                        row.col(|ui| {
-                                                                            let content = value[i].to_string();
-                                                                            let label = content.clone().replace("\n", " ");
-
-                                                                            let label: Label = Label::new(label)
-                                                                                .wrap_mode(egui::TextWrapMode::Truncate);
-                                                                            let label_widget = ui.add(label);
-
-                                                                            if label_widget.clicked() {
-                                                                                self.sql_response_copy_window.show = true;
-                                                                                self.sql_response_copy_window.response = Some(content);
-                                                                            } else if label_widget.hovered() {
-                                                                                egui::show_tooltip_at_pointer(ui.ctx(), ui.layer_id(), Id::new("copy_tooltip"), |ui| {
-                                                                                    ui.label(self.trans.click_to_copy());
+                                                if let Some(plan) = result.plan.as_ref().filter(|_| !sqlquery_page.plan_as_table) {
+                                                    ScrollArea::both().id_salt("plan").auto_shrink([false, false]).max_width(available_width).max_height(available_height).show(ui, |ui| {
+                                                        widgets::plan_tree(ui, &self.trans, plan);
+                                                    });
+                                                } else {
+                                                    let first_row = result.page_index as usize * ROWS_PER_PAGE as usize;
+                                                    let mut copy_format = None;
+                                                    let mut select_all = false;
+
+                                                    ScrollArea::horizontal().auto_shrink([false, false]).max_width(available_width).max_height(available_height).show(ui, |ui| {
+                                                        TableBuilder::new(ui)
+                                                            .striped(true)
+                                                            .auto_shrink([false, false])
+                                                            .columns(Column::remainder().resizable(true), data.columns.len())
+                                                            .header(16.0, |mut header| {
+                                                                for column in &data.columns {
+                                                                    header.col(|ui| {
+                                                                        ui.add(
+                                                                            Label::new(RichText::new(&column.name).strong().monospace())
+                                                                                .wrap_mode(egui::TextWrapMode::Extend)
+                                                                        ).on_hover_text(format!(
+                                                                            "{} (OID {}){}",
+                                                                            column.type_name,
+                                                                            column.type_oid,
+                                                                            if column.nullable == Some(false) { " NOT NULL" } else { "" },
+                                                                        ));
+                                                                    });
+                                                                }
+                                                            })
+                                                            .body(|mut body| {
+                                                                for (row_offset, values) in rows.iter().enumerate() {
+                                                                    let row_index = first_row + row_offset;
+
+                                                                    body.row(16.0, |mut row| {
+                                                                        for (column_index, value) in values.iter().enumerate() {
+                                                                            row.col(|ui| {
+                                                                                if sqlquery_page.grid_selection.is_some_and(|selection| selection.contains(row_index, column_index)) {
+                                                                                    ui.painter().rect_filled(ui.max_rect(), 0.0, ui.visuals().selection.bg_fill);
+                                                                                }
+
+                                                                                let content = value.to_string();
+
+                                                                                let text = match value {
+                                                                                    structs::ValueType::Null => RichText::new(content.clone()).italics().weak(),
+                                                                                    structs::ValueType::DecodeError(_) => RichText::new(self.trans.decode_error()).italics().color(Color32::RED),
+                                                                                    _ => RichText::new(content.replace("\n", " ")),
+                                                                                };
+
+                                                                                let label: Label = Label::new(text)
+                                                                                    .wrap_mode(egui::TextWrapMode::Truncate)
+                                                                                    .sense(Sense::click_and_drag());
+                                                                                let label_widget = ui.add(label);
+
+                                                                                // Pressing starts a selection, or extends it with shift, dragging extends it to the cell under the pointer
+                                                                                if label_widget.hovered() && ui.input(|i| i.pointer.primary_pressed()) {
+                                                                                    match sqlquery_page.grid_selection.as_mut() {
+                                                                                        Some(selection) if ui.input(|i| i.modifiers.shift) => selection.cursor = (row_index, column_index),
+                                                                                        _ => sqlquery_page.grid_selection = Some(structs::GridSelection::cell(row_index, column_index)),
+                                                                                    }
+                                                                                    sqlquery_page.selecting_cells = true;
+                                                                                } else if sqlquery_page.selecting_cells && ui.rect_contains_pointer(ui.max_rect()) {
+                                                                                    if let Some(selection) = sqlquery_page.grid_selection.as_mut() {
+                                                                                        selection.cursor = (row_index, column_index);
+                                                                                    }
+                                                                                }
+                                                                                if label_widget.secondary_clicked() && !sqlquery_page.grid_selection.is_some_and(|selection| selection.contains(row_index, column_index)) {
+                                                                                    sqlquery_page.grid_selection = Some(structs::GridSelection::cell(row_index, column_index));
+                                                                                }
+
+                                                                                if label_widget.double_clicked() {
+                                                                                    self.sql_response_copy_window.show = true;
+                                                                                    self.sql_response_copy_window.response = Some(content);
+                                                                                } else if label_widget.hovered() && !sqlquery_page.selecting_cells {
+                                                                                    egui::show_tooltip_at_pointer(ui.ctx(), ui.layer_id(), Id::new("copy_tooltip"), |ui| {
+                                                                                        if let structs::ValueType::DecodeError(e) = value {
+                                                                                            ui.label(RichText::new(e).color(Color32::RED));
+                                                                                        }
+                                                                                        ui.label(self.trans.cell_hint());
+                                                                                    });
+                                                                                }
+
+                                                                                label_widget.context_menu(|ui| {
+                                                                                    if ui.button(self.trans.copy_ctrl_c()).clicked() {
+                                                                                        copy_format = Some(export::ClipboardFormat::Tsv);
+                                                                                    }
+                                                                                    ui.menu_button(self.trans.copy_as(), |ui| {
+                                                                                        for (format, title) in [
+                                                                                            (export::ClipboardFormat::Csv, "CSV"),
+                                                                                            (export::ClipboardFormat::Json, "JSON"),
+                                                                                            (export::ClipboardFormat::Insert, "INSERT"),
+                                                                                            (export::ClipboardFormat::Markdown, "Markdown"),
+                                                                                            (export::ClipboardFormat::WhereIn, "WHERE IN (...)"),
+                                                                                        ] {
+                                                                                            if ui.button(title).clicked() {
+                                                                                                copy_format = Some(format);
+                                                                                            }
+                                                                                        }
+                                                                                    });
+                                                                                    ui.checkbox(&mut sqlquery_page.copy_headers, self.trans.include_headers());
+
+                                                                                    ui.separator();
+
+                                                                                    if ui.button(self.trans.select_all_ctrl_a()).clicked() {
+                                                                                        select_all = true;
+                                                                                        ui.close_menu();
+                                                                                    }
+                                                                                    if copy_format.is_some() {
+                                                                                        ui.close_menu();
+                                                                                    }
                                                                                 });
-                                                                            }
-                                                                        });
-                                                                    }
-                                                                });
-                                                            }
+                                                                            });
+                                                                        }
+                                                                    });
+                                                                }
+                                                            });
                                                         });
-                                                    });
+
+                                                    if !ui.input(|i| i.pointer.primary_down()) {
+                                                        sqlquery_page.selecting_cells = false;
+                                                    }
+
+                                                    // Shortcuts belong to the editor or a field while one is focused
+                                                    if ui.memory(|memory| memory.focused().is_none()) {
+                                                        if ui.input(|i| i.events.iter().any(|event| matches!(event, egui::Event::Copy))) {
+                                                            copy_format = Some(export::ClipboardFormat::Tsv);
+                                                        }
+                                                        if ui.input_mut(|i| i.consume_shortcut(&KeyboardShortcut::new(Modifiers::COMMAND, Key::A))) {
+                                                            select_all = true;
+                                                        }
+                                                    }
+
+                                                    if select_all && !data.rows.is_empty() {
+                                                        sqlquery_page.grid_selection = Some(structs::GridSelection {
+                                                            anchor: (0, 0),
+                                                            cursor: (data.rows.len() - 1, data.columns.len() - 1),
+                                                        });
+                                                    }
+
+                                                    if let (Some(format), Some(selection)) = (copy_format, sqlquery_page.grid_selection) {
+                                                        let options = export::ExportOptions {
+                                                            csv_null: self.config.settings.csv_null.clone(),
+                                                            ..self.result_export_window.options.clone()
+                                                        };
+                                                        let cells = data.slice(selection.rows(), selection.columns());
+
+                                                        ui.ctx().copy_text(export::clipboard_text(&cells, format, &options, sqlquery_page.copy_headers));
+                                                    }
+                                                }
 
                                                     if pages_count > 1 {
                                                         ui.separator();
@@ -1253,7 +2563,17 @@ impl Main<'_> {
                                                 } else {
                                                     sqlquery_page.output_is_empty = true;
 
-                                                    ui.heading(self.trans.no_data_returned());
+                                                    ui.horizontal(|ui| {
+                                                        ui.label(self.trans.success());
+                                                        ui.separator();
+                                                        ui.label(self.trans.rows_affected(result.rows_affected));
+                                                        ui.separator();
+                                                        ui.label(self.trans.time(execution_time));
+                                                    });
+
+                                                    ui.separator();
+
+                                                    ui.heading(RichText::new(result.command_tag.clone().unwrap_or_else(|| self.trans.no_data_returned())).monospace());
                                                 }
                                         }
                                         structs::SQLQueryExecutionStatusType::Error(e) => {
@@ -1271,10 +2591,154 @@ impl Main<'_> {
                                                 ui.label(self.trans.error());
                                             });
 
-                                            ui.heading(e);
+                                            match e {
+                                                database::QueryError::StatementTimeout(seconds) => {
+                                                    ui.heading(self.trans.statement_timed_out(seconds));
+                                                },
+                                                database::QueryError::LockTimeout(seconds) => {
+                                                    ui.heading(self.trans.lock_timed_out(seconds));
+                                                },
+                                                database::QueryError::Database(e) => {
+                                                    let location = e.position
+                                                        .and_then(|position| Self::error_offset(&sqlquery_page.code, &statement, position))
+                                                        .map(|offset| {
+                                                            let before = &sqlquery_page.code[..offset];
+                                                            let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+
+                                                            (before.matches('\n').count() + 1, before[line_start..].chars().count() + 1)
+                                                        });
+
+                                                    if widgets::database_error(ui, &self.trans, e, location) {
+                                                        // The cursor is moved again on the next frame
+                                                        sqlquery_page.statements[sqlquery_page.selected_statement].error_located = false;
+                                                    }
+                                                },
+                                                database::QueryError::Other(e) => {
+                                                    ui.heading(e);
+                                                },
+                                            }
+                                        }
+                                        structs::SQLQueryExecutionStatusType::Cancelled => {
+                                            ui.separator();
+
+                                            sqlquery_page.output_is_empty = true;
+
+                                            ui.label(self.trans.cancelled());
                                         }
+                                        structs::SQLQueryExecutionStatusType::Skipped => {
+                                            sqlquery_page.output_is_empty = true;
+
+                                            ui.label(self.trans.skipped());
+                                        }
+                                    }
+                                }
+                            });
+                        },
+                        structs::PageType::Listener(listener_page) => {
+                            // Notifications arrive in the background
+                            ctx.request_repaint_after(Duration::from_millis(500));
+
+                            let connected = listener_page.listener.is_connected();
+                            let error = listener_page.listener.error();
+
+                            ui.heading(self.trans.listen_notify());
+                            ui.separator();
+
+                            ui.horizontal(|ui| {
+                                if connected {
+                                    ui.label(self.trans.listening());
+                                } else if error.is_none() {
+                                    ui.spinner();
+                                    ui.label(self.trans.connecting());
+                                } else if ui.button(self.trans.reconnect()).clicked() {
+                                    listener_page.listener = listener_page.database.listener(&self.runtime, listener_page.listener.channels());
+                                }
+                            });
+
+                            if let Some(e) = error {
+                                ui.colored_label(ui.visuals().error_fg_color, e);
+                            }
+
+                            let listener = &listener_page.listener;
+
+                            ui.add_space(8.0);
+
+                            ui.horizontal(|ui| {
+                                ui.label(self.trans.channel());
+                                let field = ui.add(TextEdit::singleline(&mut listener_page.channel_field).desired_width(200.0));
+                                let submitted = field.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+
+                                if (ui.button(self.trans.listen()).clicked() || submitted) && !listener_page.channel_field.trim().is_empty() {
+                                    listener.listen(listener_page.channel_field.trim());
+                                    listener_page.channel_field.clear();
+                                }
+                            });
+
+                            ui.horizontal_wrapped(|ui| {
+                                let channels = listener.channels();
+
+                                if channels.is_empty() {
+                                    ui.label(self.trans.not_listening());
+                                }
+
+                                for channel in channels {
+                                    if ui.button(format!("{} ✖", channel)).on_hover_text(self.trans.unlisten_hint()).clicked() {
+                                        listener.unlisten(&channel);
+                                    }
+                                }
+                            });
+
+                            ui.add_space(8.0);
+
+                            ui.horizontal(|ui| {
+                                ui.label(self.trans.channel());
+                                ui.add(TextEdit::singleline(&mut listener_page.notify_channel_field).desired_width(150.0));
+                                ui.label(self.trans.payload());
+                                ui.add(TextEdit::singleline(&mut listener_page.notify_payload_field).desired_width(300.0));
+
+                                if ui.add_enabled(
+                                    connected && !listener_page.notify_channel_field.trim().is_empty(),
+                                    Button::new(self.trans.send_notify()),
+                                ).clicked() {
+                                    listener.notify(listener_page.notify_channel_field.trim(), &listener_page.notify_payload_field);
+                                }
+                            });
+
+                            ui.separator();
+
+                            listener.with_notifications(|notifications| {
+                                ui.horizontal(|ui| {
+                                    ui.label(self.trans.notifications_count(notifications.len()));
+
+                                    if ui.button(self.trans.clear_notifications()).clicked() {
+                                        listener.clear_notifications();
                                     }
+                                });
+
+                                if notifications.is_empty() {
+                                    ui.label(self.trans.no_notifications());
+                                    return;
                                 }
+
+                                Grid::new("notifications_grid").num_columns(4).striped(true).show(ui, |ui| {
+                                    ui.label(RichText::new(self.trans.received_at()).strong());
+                                    ui.label(RichText::new(self.trans.channel()).strong());
+                                    ui.label(RichText::new(self.trans.sender_pid()).strong());
+                                    ui.label(RichText::new(self.trans.payload()).strong());
+                                    ui.end_row();
+
+                                    // Newest first
+                                    for notification in notifications.iter().rev() {
+                                        ui.label(notification.time.format("%H:%M:%S%.3f").to_string());
+                                        ui.label(&notification.channel);
+                                        ui.label(notification.process_id.to_string());
+                                        match &notification.json_payload {
+                                            Some(json) => ui.label(RichText::new(json).monospace()),
+                                            None => ui.label(&notification.payload),
+                                        };
+                                        ui.end_row();
+                                    }
+                                });
                             });
                         },
                     }
@@ -1456,17 +2920,50 @@ impl App for Main<'_> {
                                                     let pages = &mut self.pages;
                                                     let server = &self.config.servers[idx];
 
-                                                    CollapsingHeader::new(&database.name).id_salt(format!("db_{}_{}_{}", server.ip, server.port, database.name)).show(ui, |ui| {
+                                                    let title = match database.tls {
+                                                        Some(_) => format!("🔒 {}", database.name),
+                                                        None => database.name.clone(),
+                                                    };
+                                                    let header = CollapsingHeader::new(title).id_salt(format!("db_{}_{}_{}", server.ip, server.port, database.name)).show(ui, |ui| {
                                                         CollapsingHeader::new(self.trans.tables()).id_salt(format!("tables_{}", database.name)).show(ui, |ui| {
                                                             for table in &database.tables {
-                                                                CollapsingHeader::new(table).id_salt(format!("table_{}_{}", database.name, table)).show(ui, |ui| {
+                                                                let table_name = table.to_string();
+                                                                CollapsingHeader::new(&table_name).id_salt(format!("table_{}_{}", database.name, table_name)).show(ui, |ui| {
+                                                                    if ui.button(self.trans.import_data()).clicked() {
+                                                                        let table_columns = Arc::new(Mutex::new(None));
+
+                                                                        let (db, table_name, columns) = (database.database.clone(), table.clone(), table_columns.clone());
+                                                                        self.runtime.spawn(async move {
+                                                                            *columns.lock().unwrap() = Some(db.get_columns(&table_name).await);
+                                                                        });
+
+                                                                        self.import_window = structs::ImportWindow {
+                                                                            database: Some(database.database.clone()),
+                                                                            table: table.clone(),
+                                                                            delimiter_field: String::from(","),
+                                                                            quote_field: String::from("\""),
+                                                                            table_columns,
+                                                                            ..Default::default()
+                                                                        };
+                                                                        self.select_file_dialog_action = Some(structs::SelectFileDialogAction::ImportCsv);
+                                                                        self.select_file_dialog.pick_file();
+                                                                    }
+                                                                    if ui.button(self.trans.export_table()).clicked() {
+                                                                        self.export_window = structs::ExportWindow {
+                                                                            show: true,
+                                                                            database: Some(database.database.clone()),
+                                                                            source: Some(database::copy::CopySource::Table(table.clone())),
+                                                                            ..Default::default()
+                                                                        };
+                                                                    }
+
                                                                     CollapsingHeader::new(self.trans.scripts()).id_salt(format!("scripts_{}_{}_{}", server.ip, database.name, table)).show(ui, |ui| {
-                                                                        widgets::script_preset(ui, pages, &database, server, "Insert", scripts::INSERT.replace("{table_name}", table));
-                                                                        widgets::script_preset(ui, pages, &database, server, "Update", scripts::UPDATE.replace("{table_name}", table));
-                                                                        widgets::script_preset(ui, pages, &database, server, "Delete", scripts::DELETE.replace("{table_name}", table));
-                                                                        widgets::script_preset(ui, pages, &database, server, "Select", scripts::SELECT.replace("{table_name}", table));
-                                                                        widgets::script_preset(ui, pages, &database, server, "Select 100", scripts::SELECT_100.replace("{table_name}", table));
-                                                                        widgets::script_preset(ui, pages, &database, server, self.trans.get_columns(), scripts::GET_TABLE_COLUMNS.replace("{table_name}", table));
+                                                                        widgets::script_preset(ui, pages, &database, server, "Insert", scripts::INSERT.replace("{table_name}", &table_name));
+                                                                        widgets::script_preset(ui, pages, &database, server, "Update", scripts::UPDATE.replace("{table_name}", &table_name));
+                                                                        widgets::script_preset(ui, pages, &database, server, "Delete", scripts::DELETE.replace("{table_name}", &table_name));
+                                                                        widgets::script_preset(ui, pages, &database, server, "Select", scripts::SELECT.replace("{table_name}", &table_name));
+                                                                        widgets::script_preset(ui, pages, &database, server, "Select 100", scripts::SELECT_100.replace("{table_name}", &table_name));
+                                                                        widgets::script_preset(ui, pages, &database, server, self.trans.get_columns(), scripts::GET_TABLE_COLUMNS.replace("{table_name}", &table_name));
                                                                     });
                                                                 });
                                                             }
@@ -1479,7 +2976,11 @@ impl App for Main<'_> {
                                                         });
 
                                                         widgets::script_preset(ui, pages, &database, server, "SQL Query", String::new());
+                                                        widgets::listener_preset(ui, pages, &self.runtime, &database, server, self.trans.listen_notify());
                                                     });
+                                                    if let Some(tls) = &database.tls {
+                                                        header.header_response.on_hover_text(self.trans.tls_connection(&tls.version, &tls.cipher));
+                                                    }
                                                 }
                                             }
                                         });
@@ -1541,6 +3042,12 @@ impl App for Main<'_> {
                                             self.edit_server_window.user_field = server.user.clone();
                                             self.edit_server_window.password_field = server.password.clone();
                                             self.edit_server_window.service_database_field = server.service_database.clone();
+                                            self.edit_server_window.statement_timeout_field = server.statement_timeout.to_string();
+                                            self.edit_server_window.lock_timeout_field = server.lock_timeout.to_string();
+                                            self.edit_server_window.ssl_mode = server.ssl_mode;
+                                            self.edit_server_window.ssl_root_cert_field = server.ssl_root_cert.clone().unwrap_or_default();
+                                            self.edit_server_window.ssl_client_cert_field = server.ssl_client_cert.clone().unwrap_or_default();
+                                            self.edit_server_window.ssl_client_key_field = server.ssl_client_key.clone().unwrap_or_default();
                                         } else if ui.button(self.trans.reload()).clicked() {
                                             let dbs = self.db_manager.dbs.clone();
                                             let config = self.config.clone();
@@ -1,8 +1,14 @@
 use egui::{
-    Context, ScrollArea, SidePanel, TopBottomPanel, Ui, RichText, Button,
+    Context, ScrollArea, SidePanel, TopBottomPanel, Ui, RichText, Button, Color32, CollapsingHeader, Id, Grid,
 };
+use egui::text::{LayoutJob, LayoutSection, TextFormat};
 
 use crate::data::structs;
+use crate::data::translates::Translator;
+use crate::database::DatabaseError;
+use crate::database::explain::{PlanNode, QueryPlan};
+
+use std::ops::Range;
 
 pub fn modal_label(ui: &mut Ui, title: impl Into<RichText>) {
     ui.vertical_centered(|ui| {
@@ -41,17 +47,203 @@ pub fn script_preset(ui: &mut Ui, pages: &mut structs::Pages, database: &structs
     if button.clicked() {
         pages.pages.push(structs::Page {
             title: String::from(format!("{} ({}:{})", database.name, server.ip, server.port)),
-            page_type: structs::PageType::SQLQuery(structs::SQLQueryPage {
+            page_type: structs::PageType::SQLQuery(Box::new(structs::SQLQueryPage {
                 database: database.database.clone(),
                 code: script.to_string(),
                 code_file_path: None,
-                sql_query_execution_status: None,
+                statements: Vec::new(),
+                selected_statement: 0,
                 output_is_empty: true,
                 update_page_index: None,
-            }),
+                fetch_action: None,
+                code_selection: None,
+                executed_range: None,
+                timeouts: None,
+                session: Default::default(),
+                parameter_values: Default::default(),
+                plan_as_table: false,
+                error_position: None,
+                grid_selection: None,
+                selecting_cells: false,
+                copy_headers: false,
+            })),
+            ..Default::default()
+        });
+
+        pages.current_page_index = (pages.pages.len() - 1) as u16;
+    }
+}
+
+pub fn listener_preset(ui: &mut Ui, pages: &mut structs::Pages, runtime: &tokio::runtime::Runtime, database: &structs::LoadedDatabase, server: &structs::Server, title: impl Into<RichText>) {
+    let button = ui.add(Button::new(title.into()));
+
+    if button.clicked() {
+        pages.pages.push(structs::Page {
+            title: format!("{} ({}:{})", database.name, server.ip, server.port),
+            page_type: structs::PageType::Listener(Box::new(structs::ListenerPage {
+                database: database.database.clone(),
+                listener: database.database.listener(runtime, Vec::new()),
+                channel_field: String::new(),
+                notify_channel_field: String::new(),
+                notify_payload_field: String::new(),
+            })),
             ..Default::default()
         });
 
         pages.current_page_index = (pages.pages.len() - 1) as u16;
     }
 }
+
+/// Changes the format of the text in the byte range, splitting the sections it crosses.
+pub fn format_range(layout_job: &mut LayoutJob, range: Range<usize>, format_text: impl Fn(&mut TextFormat)) {
+    let mut sections = Vec::with_capacity(layout_job.sections.len() + 2);
+
+    for section in layout_job.sections.drain(..) {
+        let start = section.byte_range.start.max(range.start);
+        let end = section.byte_range.end.min(range.end);
+
+        if start >= end {
+            sections.push(section);
+            continue;
+        }
+
+        let pieces = [
+            (section.byte_range.start..start, false),
+            (start..end, true),
+            (end..section.byte_range.end, false),
+        ];
+
+        let mut leading_space = section.leading_space;
+        for (byte_range, highlighted) in pieces {
+            if byte_range.is_empty() {
+                continue;
+            }
+
+            let mut format = section.format.clone();
+            if highlighted {
+                format_text(&mut format);
+            }
+
+            sections.push(LayoutSection { leading_space, byte_range, format });
+            leading_space = 0.0;
+        }
+    }
+
+    layout_job.sections = sections;
+}
+
+/// Shows the plan as a tree of collapsible nodes, coloring the nodes that take the largest share of the statement.
+pub fn plan_tree(ui: &mut Ui, trans: &Translator, plan: &QueryPlan) {
+    plan_node(ui, trans, plan, &plan.root, Id::new("plan_node"));
+
+    for (name, time) in &plan.triggers {
+        ui.label(trans.trigger_time(name, format!("{:.3}", time)));
+    }
+}
+
+fn plan_node(ui: &mut Ui, trans: &Translator, plan: &QueryPlan, node: &PlanNode, id: Id) {
+    let share = plan.share(node);
+    let measure = match node.self_time {
+        Some(self_time) => format!("{:.3} ms", self_time),
+        None => format!("{:.2}", node.self_cost),
+    };
+
+    let mut title = RichText::new(format!(
+        "{}{}  ({}, {:.0}%)",
+        node.node_type,
+        node.relation.as_ref().map(|relation| format!(" {}", relation)).unwrap_or_default(),
+        measure,
+        share * 100.0,
+    ));
+    if share >= 0.5 {
+        title = title.strong().color(Color32::RED);
+    } else if share >= 0.2 {
+        title = title.strong().color(Color32::from_rgb(230, 130, 0));
+    } else if share >= 0.1 {
+        title = title.color(Color32::from_rgb(200, 160, 0));
+    }
+
+    CollapsingHeader::new(title).id_salt(id).default_open(true).show(ui, |ui| {
+        ui.label(trans.plan_cost(format!("{:.2}", node.startup_cost), format!("{:.2}", node.total_cost), node.plan_rows));
+
+        match node.actual {
+            Some(actual) if actual.loops > 0.0 => {
+                ui.label(trans.plan_actual(
+                    format!("{:.3}", actual.startup_time),
+                    format!("{:.3}", actual.total_time),
+                    actual.rows,
+                    actual.loops,
+                ));
+            },
+            Some(_) => {
+                ui.label(RichText::new(trans.never_executed()).weak());
+            },
+            None => {},
+        }
+
+        // Estimates off by an order of magnitude usually explain a bad plan
+        if let Some(factor) = node.rows_misestimate().filter(|factor| *factor >= 10.0) {
+            ui.label(RichText::new(trans.rows_misestimate(format!("{:.0}", factor))).color(Color32::from_rgb(230, 130, 0)));
+        }
+
+        if let Some(buffers) = node.buffers {
+            ui.label(trans.plan_buffers(buffers.shared_hit, buffers.shared_read, buffers.shared_dirtied, buffers.shared_written));
+            if buffers.temp_read > 0 || buffers.temp_written > 0 {
+                ui.label(trans.plan_temp_buffers(buffers.temp_read, buffers.temp_written));
+            }
+        }
+
+        for (key, value) in &node.details {
+            ui.label(RichText::new(format!("{}: {}", key, value)).weak());
+        }
+
+        for (index, child) in node.children.iter().enumerate() {
+            plan_node(ui, trans, plan, child, id.with(index));
+        }
+    });
+}
+
+/// Shows the fields of a server error, `location` is the line and column of the failing character in the page code.
+/// Returns whether the location was clicked.
+pub fn database_error(ui: &mut Ui, trans: &Translator, error: &DatabaseError, location: Option<(usize, usize)>) -> bool {
+    let mut location_clicked = false;
+
+    ui.heading(&error.message);
+    ui.label(RichText::new(format!("{} {}", error.severity, error.code)).monospace().weak());
+
+    ui.add_space(8.0);
+
+    Grid::new("database_error").num_columns(2).show(ui, |ui| {
+        let field = |ui: &mut Ui, name: String, value: &Option<String>| {
+            if let Some(value) = value {
+                ui.label(RichText::new(name).strong());
+                ui.label(value);
+                ui.end_row();
+            }
+        };
+
+        field(ui, trans.error_detail(), &error.detail);
+        field(ui, trans.error_hint(), &error.hint);
+
+        if let Some((line, column)) = location {
+            ui.label(RichText::new(trans.error_position()).strong());
+            location_clicked = ui.link(trans.line_column(line, column)).on_hover_text(trans.go_to_error()).clicked();
+            ui.end_row();
+        }
+
+        if let Some((query, position)) = &error.internal_query {
+            ui.label(RichText::new(trans.error_internal_query()).strong());
+            ui.label(RichText::new(format!("{}\u{2192}{}", &query[..*position], &query[*position..])).monospace());
+            ui.end_row();
+        }
+
+        field(ui, trans.error_where(), &error.r#where);
+        field(ui, trans.error_schema(), &error.schema);
+        field(ui, trans.error_table(), &error.table);
+        field(ui, trans.error_column(), &error.column);
+        field(ui, trans.error_data_type(), &error.data_type);
+        field(ui, trans.error_constraint(), &error.constraint);
+    });
+
+    location_clicked
+}